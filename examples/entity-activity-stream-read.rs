@@ -48,6 +48,7 @@ async fn main() -> shotgrid_rs::Result<()> {
 
     let resp = sess
         .entity_activity_stream_read(&entity_type.unwrap(), entity_id.unwrap())
+        .execute()
         .await?;
 
     println!("Data: {:?}", resp.data);
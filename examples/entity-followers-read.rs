@@ -22,7 +22,6 @@
 //! $ cargo run --example entity-followers-read asset 12345
 //! ```
 
-use serde_json::Value;
 use shotgrid_rs::Client;
 use std::env;
 
@@ -43,12 +42,11 @@ async fn main() -> shotgrid_rs::Result<()> {
 
     let sg = Client::new(server, Some(&script_name), Some(&script_key)).expect("SG Client");
     let sess = sg.authenticate_script().await?;
-    let resp: Value = sess
+    let resp = sess
         .entity_followers_read(&entity.unwrap(), entity_id.unwrap())
         .await?;
-    println!("{}", resp);
-    for entry in resp["data"].as_array().expect("response decode") {
-        println!("{}", entry);
+    for entry in resp.data.unwrap_or_default() {
+        println!("{:?}", entry);
     }
 
     Ok(())
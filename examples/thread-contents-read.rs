@@ -23,7 +23,6 @@
 //! This example does not take any arguments for the EntityFieldsParameter.
 //!
 
-use serde_json::Value;
 use shotgrid_rs::Client;
 use std::collections::HashMap;
 use std::env;
@@ -46,12 +45,12 @@ async fn main() -> shotgrid_rs::Result<()> {
     fields.insert("entity_fields[Asset]".to_string(), "user".to_string());
     fields.insert("entity_fields[Note]".to_string(), "user".to_string());
 
-    let resp: Value = sess
+    let resp = sess
         .thread_contents_read(note_id.unwrap(), Some(fields))
         .await?;
 
-    for entry in resp["data"].as_array().expect("response decode") {
-        println!("{}", entry);
+    for entry in resp.data.unwrap_or_default() {
+        println!("{:?}", entry);
     }
 
     Ok(())
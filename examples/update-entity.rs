@@ -54,7 +54,7 @@ async fn main() -> shotgrid_rs::Result<()> {
         .update(
             &entity.unwrap(),
             entity_id.unwrap(),
-            data,
+            &data,
             Some(&return_fields.unwrap()),
         )
         .await?;
@@ -22,7 +22,6 @@
 //! $ cargo run --example user-follows-read 1600
 //! ```
 
-use serde_json::Value;
 use shotgrid_rs::Client;
 use std::env;
 
@@ -38,10 +37,10 @@ async fn main() -> shotgrid_rs::Result<()> {
 
     let sg = Client::new(server, Some(&script_name), Some(&script_key)).expect("SG Client");
     let session = sg.authenticate_script().await?;
-    let resp: Value = session.user_follows_read(user_id.unwrap()).await?;
+    let resp = session.user_follows_read(user_id.unwrap()).await?;
 
-    for entry in resp["data"].as_array().expect("response decode") {
-        println!("{}", entry);
+    for entry in resp.data.unwrap_or_default() {
+        println!("{:?}", entry);
     }
     Ok(())
 }
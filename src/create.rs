@@ -0,0 +1,167 @@
+use crate::{Result, Session};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Configures the creation of a new entity.
+///
+/// This is the return value from [`Session::create_builder()`], an
+/// alternative to [`Session::create()`].
+pub struct CreateBuilder<'a, B: ?Sized> {
+    session: &'a Session<'a>,
+    entity: &'a str,
+    data: &'a B,
+    return_fields: Option<String>,
+}
+
+impl<'a, B> CreateBuilder<'a, B>
+where
+    B: Serialize + ?Sized,
+{
+    pub fn new(session: &'a Session<'a>, entity: &'a str, data: &'a B) -> Self {
+        Self {
+            session,
+            entity,
+            data,
+            return_fields: None,
+        }
+    }
+
+    /// The fields to return for the created record.
+    ///
+    /// When not set, *all fields* are returned.
+    pub fn return_fields(mut self, fields: &[&str]) -> Self {
+        self.return_fields = Some(fields.join(","));
+        self
+    }
+
+    pub async fn execute<D>(self) -> Result<D>
+    where
+        D: DeserializeOwned + 'static,
+    {
+        self.session
+            .create(self.entity, self.data, self.return_fields.as_deref())
+            .await
+    }
+}
+
+#[cfg(test)]
+mod mock_tests {
+    use crate::Client;
+    use serde_json::{json, Value};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_return_fields() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let record_body = r##"
+        {
+          "data": {
+            "id": 1001,
+            "type": "Shot",
+            "attributes": { "code": "sh010" },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/shots/1001" }
+          },
+          "links": { "self": "/api/v1/entity/shots/1001" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot"))
+            .and(query_param("options[fields]", "id,code"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(record_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let resp: Value = session
+            .create_builder("Shot", &json!({"code": "sh010"}))
+            .return_fields(&["id", "code"])
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(resp["data"]["id"], 1001);
+    }
+
+    #[tokio::test]
+    async fn test_create_typed_body() {
+        #[derive(serde_derive::Serialize)]
+        struct NewShot<'a> {
+            code: &'a str,
+        }
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let record_body = r##"
+        {
+          "data": {
+            "id": 1001,
+            "type": "Shot",
+            "attributes": { "code": "sh010" },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/shots/1001" }
+          },
+          "links": { "self": "/api/v1/entity/shots/1001" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(record_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let resp: Value = session
+            .create("Shot", &NewShot { code: "sh010" }, None)
+            .await
+            .unwrap();
+
+        assert_eq!(resp["data"]["attributes"]["code"], "sh010");
+    }
+}
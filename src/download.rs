@@ -0,0 +1,64 @@
+//! Downloading the content of file/image fields.
+//!
+//! Reading a file field with an `alt` parameter has ShotGrid redirect to the
+//! file itself (which may be stored by ShotGrid or on S3).
+//!
+//! For more on this, refer to the ShotGrid REST API docs:
+//!
+//! <https://developer.shotgridsoftware.com/rest-api/#shotgrid-rest-api-Uploading-and-Downloading-Files>
+use crate::{handle_response, Error, Result};
+use bytes::Bytes;
+use futures::{Stream, TryStreamExt};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Response;
+use serde_json::Value;
+use std::pin::Pin;
+
+/// The body of a file download, as returned by
+/// [`Session::entity_file_field_download()`](`crate::Session::entity_file_field_download()`).
+pub struct FileDownload {
+    /// The `Content-Type` reported for the file.
+    pub content_type: Option<String>,
+    /// The `Content-Length` of the response.
+    ///
+    /// When resuming a download with a `Range` this is the length of the
+    /// *remaining* bytes rather than the whole file.
+    pub content_length: Option<u64>,
+    /// The file content, in chunks as they arrive.
+    pub stream: Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>,
+}
+
+impl std::fmt::Debug for FileDownload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileDownload")
+            .field("content_type", &self.content_type)
+            .field("content_length", &self.content_length)
+            .finish()
+    }
+}
+
+impl FileDownload {
+    pub(crate) async fn from_response(resp: Response) -> Result<Self> {
+        let status = resp.status();
+        if !status.is_success() {
+            // Error responses from ShotGrid itself will have a json payload
+            // explaining the problem. Failing that, go with the status.
+            return Err(match handle_response::<Value>(resp).await {
+                Err(Error::JsonParse(_)) | Ok(_) => {
+                    Error::Unexpected(format!("Download failed with status `{}`", status))
+                }
+                Err(err) => err,
+            });
+        }
+
+        Ok(FileDownload {
+            content_type: resp
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|val| val.to_str().ok())
+                .map(String::from),
+            content_length: resp.content_length(),
+            stream: Box::pin(resp.bytes_stream().map_err(Error::from)),
+        })
+    }
+}
@@ -0,0 +1,151 @@
+use crate::types::EntityActivityStreamResponse;
+use crate::{Result, Session};
+
+pub struct EntityActivityStreamReadReqBuilder<'a> {
+    session: &'a Session<'a>,
+    entity: &'a str,
+    entity_id: i32,
+    limit: Option<usize>,
+    min_id: Option<i32>,
+    max_id: Option<i32>,
+    entity_fields: Vec<(String, &'a str)>,
+}
+
+impl<'a> EntityActivityStreamReadReqBuilder<'a> {
+    pub fn new(session: &'a Session<'a>, entity: &'a str, entity_id: i32) -> Self {
+        Self {
+            session,
+            entity,
+            entity_id,
+            limit: None,
+            min_id: None,
+            max_id: None,
+            entity_fields: vec![],
+        }
+    }
+
+    /// The most updates to return.
+    pub fn limit(mut self, value: usize) -> Self {
+        self.limit = Some(value);
+        self
+    }
+
+    /// Only return updates with an id greater than or equal to this.
+    pub fn min_id(mut self, value: i32) -> Self {
+        self.min_id = Some(value);
+        self
+    }
+
+    /// Only return updates with an id less than or equal to this.
+    ///
+    /// To page back through the history of a busy entity, set this to one
+    /// less than the `earliest_update_id` from the previous response.
+    pub fn max_id(mut self, value: i32) -> Self {
+        self.max_id = Some(value);
+        self
+    }
+
+    /// Extra fields to return for entities of the given type referenced by
+    /// the updates, as a comma separated list.
+    ///
+    /// Can be called once per entity type.
+    pub fn entity_fields(mut self, entity: &str, fields: &'a str) -> Self {
+        self.entity_fields
+            .push((format!("entity_fields[{}]", entity), fields));
+        self
+    }
+
+    pub async fn execute(self) -> Result<EntityActivityStreamResponse> {
+        let (sg, token) = self.session.get_sg().await?;
+        let mut req = sg
+            .http
+            .get(&format!(
+                "{}{}/entity/{}/{}/activity_stream",
+                sg.sg_server, sg.api_base, self.entity, self.entity_id
+            ))
+            .bearer_auth(&token)
+            .header("Accept", "application/json");
+        if let Some(val) = self.limit {
+            req = req.query(&[("limit", val)]);
+        }
+        if let Some(val) = self.min_id {
+            req = req.query(&[("min_id", val)]);
+        }
+        if let Some(val) = self.max_id {
+            req = req.query(&[("max_id", val)]);
+        }
+        for (key, fields) in &self.entity_fields {
+            req = req.query(&[(key, fields)]);
+        }
+        sg.send_idempotent(req).await
+    }
+}
+
+#[cfg(test)]
+mod mock_tests {
+    use crate::Client;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_paging_params() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let stream_body = r##"
+        {
+          "data": {
+            "entity_type": "Shot",
+            "entity_id": 123,
+            "latest_update_id": 456,
+            "earliest_update_id": 455,
+            "updates": []
+          },
+          "links": { "self": "/api/v1/entity/shots/123/activity_stream" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/123/activity_stream"))
+            .and(query_param("limit", "2"))
+            .and(query_param("min_id", "100"))
+            .and(query_param("max_id", "456"))
+            .and(query_param("entity_fields[Version]", "code,sg_status_list"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(stream_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let resp = session
+            .entity_activity_stream_read("Shot", 123)
+            .limit(2)
+            .min_id(100)
+            .max_id(456)
+            .entity_fields("Version", "code,sg_status_list")
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(resp.data.unwrap().earliest_update_id, Some(455));
+    }
+}
@@ -1,13 +1,21 @@
-use crate::types::{OptionsParameter, ReturnOnly};
-use crate::{handle_response, Result, Session};
+use crate::paginate::paginate;
+use crate::types::{
+    OptionsParameter, PaginationLinks, PaginationParameter, ResourceArrayResponse, ReturnOnly,
+};
+use crate::{Result, ReturnFields, ReturnFieldsTarget, Session};
+use futures::stream::Stream;
 use serde::de::DeserializeOwned;
 
+#[derive(Clone)]
 pub struct EntityRelationshipReadReqBuilder<'a> {
     session: &'a Session<'a>,
     entity: &'a str,
     entity_id: i32,
     related_field: &'a str,
+    fields: Option<&'a str>,
     options: OptionsParameter,
+    pagination: PaginationParameter,
+    sort: Option<String>,
 }
 
 impl<'a> EntityRelationshipReadReqBuilder<'a> {
@@ -22,10 +30,35 @@ impl<'a> EntityRelationshipReadReqBuilder<'a> {
             entity,
             entity_id,
             related_field,
+            fields: None,
             options: OptionsParameter::default(),
+            pagination: PaginationParameter::default(),
+            sort: None,
         }
     }
 
+    /// A comma separated list of the fields to return for each related
+    /// record, rather than all of them.
+    pub fn fields(mut self, value: &'a str) -> Self {
+        self.fields = Some(value);
+        self
+    }
+
+    pub fn sort(mut self, value: Option<&'a str>) -> Self {
+        self.sort = value.map(|f| f.to_string());
+        self
+    }
+
+    pub fn size(mut self, value: Option<usize>) -> Self {
+        self.pagination.size = value;
+        self
+    }
+
+    pub fn number(mut self, value: Option<usize>) -> Self {
+        self.pagination.number = value;
+        self
+    }
+
     pub fn return_only(mut self, value: Option<ReturnOnly>) -> Self {
         self.options.return_only = value;
         self
@@ -40,15 +73,28 @@ impl<'a> EntityRelationshipReadReqBuilder<'a> {
     where
         D: DeserializeOwned + 'static,
     {
+        self.pagination.validate()?;
         let (sg, token) = self.session.get_sg().await?;
         let mut req = sg
             .http
             .get(&format!(
-                "{}/api/v1/entity/{}/{}/relationships/{}",
-                sg.sg_server, self.entity, self.entity_id, self.related_field
+                "{}{}/entity/{}/{}/relationships/{}",
+                sg.sg_server, sg.api_base, self.entity, self.entity_id, self.related_field
             ))
             .bearer_auth(&token)
             .header("Accept", "application/json");
+        if let Some(val) = self.fields {
+            req = req.query(&[ReturnFields::parse(val).query(ReturnFieldsTarget::Read)]);
+        }
+        if let Some(val) = self.pagination.number {
+            req = req.query(&[("page[number]", val)]);
+        }
+        if let Some(val) = self.pagination.size {
+            req = req.query(&[("page[size]", val)]);
+        }
+        if let Some(val) = self.sort {
+            req = req.query(&[("sort", val)]);
+        }
         if let Some(val) = self.options.include_archived_projects {
             req = req.query(&[("options[include_archived_projects]", val)]);
         }
@@ -61,6 +107,180 @@ impl<'a> EntityRelationshipReadReqBuilder<'a> {
                 },
             )]);
         }
-        handle_response(req.send().await?).await
+        sg.send_idempotent(req).await
+    }
+
+    /// Read the related records one at a time, following the pagination
+    /// links until there are no more pages.
+    ///
+    /// Paging starts from the page set by
+    /// [`number()`](`EntityRelationshipReadReqBuilder::number()`) (or the first
+    /// page), with each page holding as many records as set by
+    /// [`size()`](`EntityRelationshipReadReqBuilder::size()`).
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> shotgrid_rs::Result<()> {
+    /// # use shotgrid_rs::Client;
+    /// # use shotgrid_rs::types::Entity;
+    /// use futures::TryStreamExt;
+    ///
+    /// # let sg = Client::new(String::from("https://shotgrid.example.com"), None, None)?;
+    /// # let session = sg.authenticate_script().await?;
+    /// let versions: Vec<Entity> = session
+    ///     .entity_relationship_read("Playlist", 123, "versions")
+    ///     .stream()
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream<R>(self) -> impl Stream<Item = Result<R>> + 'a
+    where
+        R: DeserializeOwned + 'static,
+    {
+        let first_page = self.pagination.number.unwrap_or(1);
+        paginate(first_page, move |page| {
+            self.clone()
+                .number(Some(page))
+                .execute::<ResourceArrayResponse<R, PaginationLinks>>()
+        })
+    }
+}
+
+#[cfg(test)]
+mod mock_tests {
+    use crate::Client;
+    use serde_json::Value;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_paging_and_sort_params() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let body = r##"
+        {
+          "data": [{ "id": 456, "type": "Asset" }],
+          "links": { "self": "/api/v1/entity/shots/123/relationships/assets" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/123/relationships/assets"))
+            .and(query_param("page[number]", "3"))
+            .and(query_param("page[size]", "100"))
+            .and(query_param("sort", "-id"))
+            .and(query_param("fields", "code,sg_status_list"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let resp: Value = session
+            .entity_relationship_read("Shot", 123, "assets")
+            .fields("code,sg_status_list")
+            .sort(Some("-id"))
+            .size(Some(100))
+            .number(Some(3))
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(resp["data"][0]["id"], 456);
+    }
+
+    #[tokio::test]
+    async fn test_stream_walks_pages() {
+        use crate::types::Entity;
+        use futures::TryStreamExt;
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let page_1 = r##"
+        {
+          "data": [{ "id": 1, "type": "Version" }, { "id": 2, "type": "Version" }],
+          "links": {
+            "self": "/api/v1/entity/playlists/123/relationships/versions?page[number]=1",
+            "next": "/api/v1/entity/playlists/123/relationships/versions?page[number]=2"
+          }
+        }
+        "##;
+        let page_2 = r##"
+        {
+          "data": [{ "id": 3, "type": "Version" }],
+          "links": {
+            "self": "/api/v1/entity/playlists/123/relationships/versions?page[number]=2"
+          }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Playlist/123/relationships/versions"))
+            .and(query_param("page[number]", "1"))
+            .and(query_param("page[size]", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(page_1, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Playlist/123/relationships/versions"))
+            .and(query_param("page[number]", "2"))
+            .and(query_param("page[size]", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(page_2, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let versions: Vec<Entity> = session
+            .entity_relationship_read("Playlist", 123, "versions")
+            .size(Some(2))
+            .stream()
+            .try_collect()
+            .await
+            .unwrap();
+
+        let ids: Vec<i32> = versions.iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
     }
 }
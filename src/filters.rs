@@ -143,6 +143,9 @@
 //! - linked to one of a series of projects
 //! - status is not yet approved
 //!
+//! When records should match *any* of the conditions instead, use
+//! [`basic_any()`].
+//!
 //! # Complex Filters
 //!
 //! Complex filters are created with [`complex()`] and are comprised of a
@@ -183,6 +186,10 @@
 //! > For this reason, [`complex()`] returns a `Result` and will give an `Err`
 //! > if a *filter* is supplied as the root instead of a *logical filter operator*.
 //!
+//! [`complex()`] will also give an `Err` if [`and()`]/[`or()`] are nested
+//! deeper than [`DEFAULT_MAX_FILTER_DEPTH`]. Use [`complex_with_max_depth()`]
+//! to allow for more (or less) nesting.
+//!
 //! ## `Filter` vs `ComplexFilter`
 //!
 //! Distinct types are used to help enforce the more strict rules that separate
@@ -229,12 +236,15 @@
 //! - <https://developer.shotgridsoftware.com/rest-api/#filtering>
 //! - <https://developer.shotgridsoftware.com/python-api/reference.html#filter-syntax>
 
+use crate::types::Entity;
 use serde::{
     ser::{SerializeMap, SerializeSeq},
     Serialize, Serializer,
 };
 
+/// Content type for request bodies carrying [`basic()`] filters.
 pub const MIME_FILTER_ARRAY: &str = "application/vnd+shotgun.api3_array+json";
+/// Content type for request bodies carrying [`complex()`] filters.
 pub const MIME_FILTER_HASH: &str = "application/vnd+shotgun.api3_hash+json";
 
 impl From<Filter> for ComplexFilter {
@@ -256,6 +266,26 @@ pub fn basic(filters: &[Filter]) -> FinalizedFilters {
     FinalizedFilters::Basic(filters.to_vec())
 }
 
+/// Like [`basic()`], but records matching these filters need only satisfy
+/// *any* of the specified conditions.
+///
+/// ShotGrid's REST API has no way to "or" the conditions of an array
+/// (basic) filter, so this is shorthand for [`complex()`] with an [`or()`]
+/// root, and is sent as a hash filter.
+///
+/// ```
+/// use shotgrid_rs::filters::{self, field};
+///
+/// let in_progress = filters::basic_any(&[
+///     field("sg_status_list").is("ip"),
+///     field("sg_status_list").is("rev"),
+/// ]);
+/// assert!(in_progress.is_complex());
+/// ```
+pub fn basic_any(filters: &[Filter]) -> FinalizedFilters {
+    FinalizedFilters::Complex(or(filters))
+}
+
 /// The "complex" filter constructor. Accepts a "root" which should be a
 /// [`ComplexFilter`] such as [`and()`] or [`or()`].
 ///
@@ -263,16 +293,57 @@ pub fn basic(filters: &[Filter]) -> FinalizedFilters {
 /// filters produced by [`field()`].
 ///
 /// Will return `Err` if the root is a filter (rather than an [`and()`] or
-/// [`or()`]).
+/// [`or()`]), or if the [`and()`]/[`or()`] are nested deeper than
+/// [`DEFAULT_MAX_FILTER_DEPTH`].
 pub fn complex(root: ComplexFilter) -> crate::Result<FinalizedFilters> {
-    match root {
-        ComplexFilter::LogicalFilterOperator(_) => {}
-        _ => return Err(crate::Error::InvalidFilters),
+    complex_with_max_depth(root, DEFAULT_MAX_FILTER_DEPTH)
+}
+
+/// The deepest [`complex()`] will allow [`and()`]/[`or()`] to be nested, with
+/// the root counting as the first level.
+///
+/// ShotGrid doesn't document a maximum, but rejects filters nested too deeply
+/// with a generic error. This limit is a conservative one of our own, well
+/// beyond what hand written filters need, so runaway filters built up in
+/// code are caught with a clearer message before they're sent.
+pub const DEFAULT_MAX_FILTER_DEPTH: usize = 8;
+
+/// Like [`complex()`], but with a custom limit for how deeply [`and()`]/[`or()`]
+/// may be nested.
+///
+/// ```
+/// use shotgrid_rs::filters::{self, and, field, or};
+///
+/// let root = and(&[or(&[and(&[field("code").is("sh010")])])]);
+/// assert!(filters::complex_with_max_depth(root.clone(), 2).is_err());
+/// assert!(filters::complex_with_max_depth(root, 3).is_ok());
+/// ```
+pub fn complex_with_max_depth(
+    root: ComplexFilter,
+    max_depth: usize,
+) -> crate::Result<FinalizedFilters> {
+    check_complex_root(&root)?;
+
+    let depth = root.depth();
+    if depth > max_depth {
+        return Err(crate::Error::InvalidFilters(format!(
+            "`and()`/`or()` are nested {} levels deep, but at most {} are allowed",
+            depth, max_depth
+        )));
     }
 
     Ok(FinalizedFilters::Complex(root))
 }
 
+fn check_complex_root(root: &ComplexFilter) -> crate::Result<()> {
+    match root {
+        ComplexFilter::LogicalFilterOperator(_) => Ok(()),
+        _ => Err(crate::Error::InvalidFilters(String::from(
+            "the root of complex filters must be `and()` or `or()`, not a filter",
+        ))),
+    }
+}
+
 /// Sometimes you don't really want to filter by anything!
 /// We got you. Use an *empty* in this situation. It's wide open.
 pub fn empty() -> FinalizedFilters {
@@ -288,12 +359,57 @@ pub enum FinalizedFilters {
 }
 
 impl FinalizedFilters {
+    /// The content type ShotGrid expects for a request body carrying these
+    /// filters.
+    ///
+    /// Basic filters are sent as [`MIME_FILTER_ARRAY`] and complex filters are
+    /// sent as [`MIME_FILTER_HASH`].
+    ///
+    /// The query methods on [`Session`](`crate::Session`) take care of this
+    /// for you, but if you're building your own requests (for example with the
+    /// re-exported [`reqwest`](`crate::transport::reqwest`)), you'll want to
+    /// set the `Content-Type` header to this value.
+    ///
+    /// ```
+    /// use shotgrid_rs::filters::{self, field, MIME_FILTER_ARRAY};
+    ///
+    /// let filters = filters::basic(&[field("sg_status_list").is("apr")]);
+    /// assert_eq!(MIME_FILTER_ARRAY, filters.get_mime());
+    /// ```
     pub fn get_mime(&self) -> &'static str {
         match self {
             Self::Basic(_) => MIME_FILTER_ARRAY,
             Self::Complex(_) => MIME_FILTER_HASH,
         }
     }
+
+    /// Returns `true` for filters produced by [`complex()`], and `false` for
+    /// those produced by [`basic()`] (or [`empty()`]).
+    pub fn is_complex(&self) -> bool {
+        matches!(self, Self::Complex(_))
+    }
+
+    /// The filters as JSON, exactly as they'll be sent to ShotGrid.
+    ///
+    /// This is the same as the [`Display`](`std::fmt::Display`) output, handy
+    /// for logging.
+    ///
+    /// ```
+    /// use shotgrid_rs::filters::{self, field};
+    ///
+    /// let filters = filters::basic(&[field("sg_status_list").is("apr")]);
+    /// assert_eq!(r#"[["sg_status_list","is","apr"]]"#, filters.to_json_string());
+    /// ```
+    pub fn to_json_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl std::fmt::Display for FinalizedFilters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        f.write_str(&json)
+    }
 }
 
 /// These represent the groupings of filter clauses.
@@ -313,6 +429,19 @@ pub enum ComplexFilter {
     Filter(Filter),
 }
 
+impl ComplexFilter {
+    /// How many levels of [`and()`]/[`or()`] deep this goes.
+    fn depth(&self) -> usize {
+        match self {
+            ComplexFilter::Filter(_) => 0,
+            ComplexFilter::LogicalFilterOperator(LogicalFilterOperator::And(conditions))
+            | ComplexFilter::LogicalFilterOperator(LogicalFilterOperator::Or(conditions)) => {
+                1 + conditions.iter().map(Self::depth).max().unwrap_or(0)
+            }
+        }
+    }
+}
+
 pub fn and<F>(conditions: &[F]) -> ComplexFilter
 where
     F: Into<ComplexFilter> + Clone,
@@ -375,6 +504,26 @@ impl EntityRef {
     }
 }
 
+/// Renders as `Type:id`, such as `Asset:123`.
+impl std::fmt::Display for EntityRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.r#type, self.id)
+    }
+}
+
+impl From<Entity> for EntityRef {
+    fn from(x: Entity) -> Self {
+        let Entity { r#type, id } = x;
+        Self { r#type, id }
+    }
+}
+
+impl From<&EntityRef> for Entity {
+    fn from(x: &EntityRef) -> Self {
+        Entity::new(x.r#type.clone(), x.id)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Filter {
     Is {
@@ -433,6 +582,10 @@ pub enum Filter {
         field: String,
         values: Vec<FieldValue>,
     },
+    NotIn {
+        field: String,
+        values: Vec<FieldValue>,
+    },
     TypeIs {
         field: String,
         // The docs call for this to be optional, but how the heck can a record
@@ -567,6 +720,11 @@ impl Serialize for Filter {
                 state.serialize_element("in")?;
                 state.serialize_element(&values)?;
             }
+            Filter::NotIn { field, values } => {
+                state.serialize_element(&field)?;
+                state.serialize_element("not_in")?;
+                state.serialize_element(&values)?;
+            }
             Filter::TypeIs { field, value } => {
                 state.serialize_element(&field)?;
                 state.serialize_element("type_is")?;
@@ -617,6 +775,124 @@ impl Serialize for Filter {
     }
 }
 
+/// Matches records where the status list `field` (usually `sg_status_list`)
+/// holds any one of the given status short codes.
+///
+/// ```
+/// use shotgrid_rs::filters::{self, field};
+///
+/// let active = filters::basic(&[
+///     filters::status_in("sg_status_list", &["ip", "rev"]),
+///     field("code").starts_with("sh"),
+/// ]);
+/// ```
+pub fn status_in(field: &str, statuses: &[&str]) -> Filter {
+    self::field(field).in_(statuses)
+}
+
+/// Matches records where the status list `field` (usually `sg_status_list`)
+/// holds none of the given status short codes.
+pub fn status_not_in(field: &str, statuses: &[&str]) -> Filter {
+    self::field(field).not_in(statuses)
+}
+
+/// Matches records which have (or, when `present` is false, lack) a
+/// thumbnail.
+///
+/// Thumbnails live in the special `image` field, not in fields such as
+/// `sg_uploaded_movie` which hold the media a thumbnail may have been made
+/// from.
+///
+/// ```
+/// use shotgrid_rs::filters;
+///
+/// let needs_thumbnail = filters::basic(&[filters::has_thumbnail(false)]);
+/// ```
+pub fn has_thumbnail(present: bool) -> Filter {
+    if present {
+        field("image").is_not(FieldValue::None)
+    } else {
+        field("image").is(FieldValue::None)
+    }
+}
+
+/// A studio's split of status short codes into open and closed work.
+///
+/// ShotGrid has no notion of status categories in its filters, so this keeps
+/// the list of "closed" statuses in one place and builds the [`status_in()`]
+/// and [`status_not_in()`] filters from it, for tools that all need to agree
+/// on what counts as done.
+///
+/// ```
+/// use shotgrid_rs::filters::{self, StatusCategories};
+///
+/// let categories = StatusCategories::new(&["fin", "omt", "cmpt"]);
+/// let open_tasks = filters::basic(&[categories.open()]);
+/// let closed_versions = filters::basic(&[categories.closed()]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StatusCategories {
+    field: String,
+    closed: Vec<String>,
+}
+
+impl StatusCategories {
+    /// Build the categories from the statuses counting as closed, for the
+    /// `sg_status_list` field.
+    pub fn new(closed: &[&str]) -> Self {
+        Self {
+            field: String::from("sg_status_list"),
+            closed: closed.iter().map(|status| status.to_string()).collect(),
+        }
+    }
+
+    /// Filter on some other status list field, rather than `sg_status_list`.
+    pub fn with_field<S: Into<String>>(mut self, field: S) -> Self {
+        self.field = field.into();
+        self
+    }
+
+    /// Matches records with any status other than the closed ones.
+    pub fn open(&self) -> Filter {
+        status_not_in(&self.field, &self.closed_statuses())
+    }
+
+    /// Matches records with one of the closed statuses.
+    pub fn closed(&self) -> Filter {
+        status_in(&self.field, &self.closed_statuses())
+    }
+
+    fn closed_statuses(&self) -> Vec<&str> {
+        self.closed.iter().map(String::as_str).collect()
+    }
+}
+
+/// Matches records where `field` falls in the half-open range from `start`
+/// up to, but not including, `end_exclusive`.
+///
+/// ShotGrid has no "greater than or equal" operator, and
+/// [`Field::between()`] includes both ends, so this pairs `between` with an
+/// `is_not` on the upper bound. Since consecutive ranges share their bounds,
+/// "this week" and "next week" queries built this way never overlap and
+/// don't need the bounds adjusted by hand.
+///
+/// ```
+/// use shotgrid_rs::filters::{self, field};
+///
+/// let this_week = filters::basic(
+///     &filters::date_range_half_open("due_date", "2021-03-01", "2021-03-08"),
+/// );
+/// ```
+pub fn date_range_half_open<V>(field: &str, start: V, end_exclusive: V) -> Vec<Filter>
+where
+    V: Into<FieldValue> + Clone,
+{
+    vec![
+        self::field(field).between(start, end_exclusive.clone()),
+        self::field(field).is_not(end_exclusive),
+    ]
+}
+
 pub fn field<S: Into<String>>(name: S) -> Field {
     Field { field: name.into() }
 }
@@ -708,6 +984,9 @@ impl Field {
         }
     }
 
+    /// Matches values from `lower` to `upper`, including both.
+    ///
+    /// See [`date_range_half_open()`] to leave out the upper bound.
     pub fn between<V>(self, lower: V, upper: V) -> Filter
     where
         V: Into<FieldValue>,
@@ -719,6 +998,7 @@ impl Field {
         }
     }
 
+    /// Matches values outside `lower` to `upper`, so neither bound is matched.
     pub fn not_between<V>(self, lower: V, upper: V) -> Filter
     where
         V: Into<FieldValue>,
@@ -756,13 +1036,56 @@ impl Field {
         }
     }
 
+    /// Matches when the field's value is any one of `values`.
+    ///
+    /// Take care when `values` might be empty. The filter is still sent as-is
+    /// (ie. `["x", "in", []]`) but ShotGrid doesn't treat this consistently;
+    /// some endpoints will match nothing, others will reject the filter
+    /// outright. A warning is logged when this happens, but you'll likely
+    /// want to skip the request entirely when there's nothing to match.
     pub fn in_<V>(self, values: &[V]) -> Filter
     where
         V: Into<FieldValue> + Clone,
     {
+        self.in_iter(values.iter().cloned())
+    }
+
+    /// The same as [`Field::in_()`], but taking the values from an iterator
+    /// so they needn't be collected first.
+    ///
+    /// ```
+    /// use shotgrid_rs::filters::{self, field};
+    /// use shotgrid_rs::types::Entity;
+    ///
+    /// let shots = vec![Entity::new("Shot", 1), Entity::new("Shot", 2)];
+    /// let filters = filters::basic(&[field("id").in_iter(shots.iter().map(|s| s.id))]);
+    /// ```
+    pub fn in_iter<I, V>(self, values: I) -> Filter
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<FieldValue>,
+    {
+        let values: Vec<FieldValue> = values.into_iter().map(Into::into).collect();
+        if values.is_empty() {
+            log::warn!(
+                "Filter `in` for field `{}` given no values; results will vary by endpoint.",
+                self.field
+            );
+        }
         Filter::In {
             field: self.field,
-            values: values.to_vec().into_iter().map(Into::into).collect(),
+            values,
+        }
+    }
+
+    /// Matches when the field's value is none of `values`.
+    pub fn not_in<V>(self, values: &[V]) -> Filter
+    where
+        V: Into<FieldValue> + Clone,
+    {
+        Filter::NotIn {
+            field: self.field,
+            values: values.iter().cloned().map(Into::into).collect(),
         }
     }
 
@@ -852,6 +1175,76 @@ impl Field {
             value: value.into(),
         }
     }
+
+    /// Match the display name of linked entities against a simple glob pattern.
+    ///
+    /// ShotGrid's filter grammar has no regex (or glob) operator, so this
+    /// decomposes the pattern into [`Field::name_starts_with()`],
+    /// [`Field::name_ends_with()`] and [`Field::name_contains()`] conditions.
+    /// The returned filters should all be satisfied, so they can be passed to
+    /// [`basic()`] or [`and()`] as-is.
+    ///
+    /// Supported patterns are:
+    ///
+    /// - `abc*` (starts with)
+    /// - `*abc` (ends with)
+    /// - `*abc*` (contains)
+    /// - `abc*xyz` (starts with *and* ends with)
+    /// - `*` (matches anything, producing no filters)
+    ///
+    /// Anything else, including patterns with no `*`, more than one literal
+    /// between wildcards, or the `?` and `[...]` glob syntax, can't be expressed
+    /// and returns [`Error::UnsupportedGlob`](`crate::Error::UnsupportedGlob`).
+    ///
+    /// > Note that `abc*xyz` won't enforce the prefix and suffix don't overlap,
+    /// > so `ab*bc` will also match `abc`.
+    ///
+    /// ```
+    /// use shotgrid_rs::filters::{self, field};
+    ///
+    /// let filters = filters::basic(&field("entity").name_matches_glob("bub_*_v01").unwrap());
+    /// assert!(field("entity").name_matches_glob("bub_?").is_err());
+    /// ```
+    pub fn name_matches_glob<S>(self, pattern: S) -> crate::Result<Vec<Filter>>
+    where
+        S: AsRef<str>,
+    {
+        let pattern = pattern.as_ref();
+        let unsupported = || Err(crate::Error::UnsupportedGlob(pattern.to_string()));
+
+        if pattern.contains(&['?', '[', ']'][..]) {
+            return unsupported();
+        }
+
+        let parts: Vec<&str> = pattern.split('*').collect();
+        if parts.len() < 2 {
+            // No wildcard at all, which would need an exact name match.
+            return unsupported();
+        }
+
+        let (first, last) = (parts[0], parts[parts.len() - 1]);
+        let middle: Vec<&str> = parts[1..parts.len() - 1]
+            .iter()
+            .copied()
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        match (first, middle.as_slice(), last) {
+            ("", [], "") => Ok(vec![]),
+            ("", [inner], "") => Ok(vec![field(self.field).name_contains(*inner)]),
+            (_, [], _) => {
+                let mut filters = vec![];
+                if !first.is_empty() {
+                    filters.push(field(self.field.clone()).name_starts_with(first));
+                }
+                if !last.is_empty() {
+                    filters.push(field(self.field).name_ends_with(last));
+                }
+                Ok(filters)
+            }
+            _ => unsupported(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -865,7 +1258,21 @@ pub enum FieldValue {
     UInt32(u32),
     UInt64(u64),
     String(String),
-    EntityRef { r#type: String, id: i32 },
+    EntityRef {
+        r#type: String,
+        id: i32,
+    },
+    /// A list of values, for `list`, `tag_list`, or multi-entity fields.
+    ///
+    /// Can be built from a `Vec` of any of the types that convert to
+    /// `FieldValue`:
+    ///
+    /// ```
+    /// use shotgrid_rs::filters::{field, EntityRef};
+    ///
+    /// field("tags").contains(vec![EntityRef::new("Tag", 1), EntityRef::new("Tag", 2)]);
+    /// ```
+    Array(Vec<FieldValue>),
     None,
 }
 
@@ -1128,6 +1535,49 @@ impl From<Option<String>> for FieldValue {
         }
     }
 }
+impl<T> From<Vec<T>> for FieldValue
+where
+    T: Into<FieldValue>,
+{
+    fn from(x: Vec<T>) -> Self {
+        FieldValue::Array(x.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Dates are sent as `YYYY-MM-DD`, as used by `date` fields.
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for FieldValue {
+    fn from(x: chrono::NaiveDate) -> Self {
+        FieldValue::String(x.format("%Y-%m-%d").to_string())
+    }
+}
+#[cfg(feature = "chrono")]
+impl From<Option<chrono::NaiveDate>> for FieldValue {
+    fn from(x: Option<chrono::NaiveDate>) -> Self {
+        match x {
+            None => FieldValue::None,
+            Some(x) => x.into(),
+        }
+    }
+}
+
+/// Times are sent as RFC 3339 timestamps in UTC, such as
+/// `2021-03-01T17:30:00Z`, as used by `date_time` fields.
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for FieldValue {
+    fn from(x: chrono::DateTime<chrono::Utc>) -> Self {
+        FieldValue::String(x.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+    }
+}
+#[cfg(feature = "chrono")]
+impl From<Option<chrono::DateTime<chrono::Utc>>> for FieldValue {
+    fn from(x: Option<chrono::DateTime<chrono::Utc>>) -> Self {
+        match x {
+            None => FieldValue::None,
+            Some(x) => x.into(),
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -1261,6 +1711,70 @@ mod tests {
         assert_eq!(&expected, &serde_json::json!(filters));
     }
 
+    #[test]
+    fn test_basic_any_filters() {
+        let filters = basic_any(&[
+            field("sg_status_list").is("ip"),
+            field("sg_status_list").is("rev"),
+        ]);
+        let expected = serde_json::json!({
+            "logical_operator": "or",
+            "conditions": [
+                ["sg_status_list", "is", "ip"],
+                ["sg_status_list", "is", "rev"],
+            ]
+        });
+        assert_eq!(&expected, &serde_json::json!(filters));
+        assert_eq!(MIME_FILTER_HASH, filters.get_mime());
+    }
+
+    #[test]
+    fn test_finalized_filters_display() {
+        let filters = complex(or(&[
+            field("sg_status_list").is("apr"),
+            field("sg_status_list").is("ip"),
+        ]))
+        .unwrap();
+        let expected = r#"{"logical_operator":"or","conditions":[["sg_status_list","is","apr"],["sg_status_list","is","ip"]]}"#;
+        assert_eq!(expected, filters.to_json_string());
+        assert_eq!(expected, format!("{}", filters));
+        assert_eq!("[]", empty().to_string());
+    }
+
+    #[test]
+    fn test_complex_filters_depth() {
+        let mut root = or(&[field("code").is("sh010")]);
+        for _ in 1..DEFAULT_MAX_FILTER_DEPTH {
+            root = and(&[root, field("code").is("sh020").into()]);
+        }
+        assert_eq!(root.depth(), DEFAULT_MAX_FILTER_DEPTH);
+        assert!(complex(root.clone()).is_ok());
+        assert!(complex_with_max_depth(and(&[root.clone()]), 9).is_ok());
+
+        match complex(and(&[root])) {
+            Err(crate::Error::InvalidFilters(msg)) => {
+                assert!(msg.contains("nested 9 levels deep"), "{}", msg)
+            }
+            other => panic!("Expected Error::InvalidFilters, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_finalized_filters_mime() {
+        let basic_filters = basic(&[field("sg_status_list").is("apr")]);
+        let complex_filters = complex(or(&[
+            field("sg_status_list").is("apr"),
+            field("sg_status_list").is("ip"),
+        ]))
+        .unwrap();
+
+        assert!(!basic_filters.is_complex());
+        assert!(!empty().is_complex());
+        assert!(complex_filters.is_complex());
+        assert_eq!(MIME_FILTER_ARRAY, basic_filters.get_mime());
+        assert_eq!(MIME_FILTER_HASH, complex_filters.get_mime());
+    }
+
     #[test]
     fn test_field_kitchen_sink_is() {
         let filters = basic(&[
@@ -1328,6 +1842,23 @@ mod tests {
         assert_eq!(&expected, &serde_json::json!(filters));
     }
 
+    #[test]
+    fn test_array_values() {
+        let filters = basic(&[
+            field("tags").contains(vec![EntityRef::new("Tag", 1), EntityRef::new("Tag", 2)]),
+            field("sg_frames").is(vec![1001, 1002]),
+            field("sg_labels").is(vec![Some("hero"), None]),
+            field("sg_labels").is(Vec::<&str>::new()),
+        ]);
+        let expected = serde_json::json!([
+            ["tags", "contains", [{ "type": "Tag", "id": 1 }, { "type": "Tag", "id": 2 }]],
+            ["sg_frames", "is", [1001, 1002]],
+            ["sg_labels", "is", ["hero", null]],
+            ["sg_labels", "is", []],
+        ]);
+        assert_eq!(&expected, &serde_json::json!(filters));
+    }
+
     #[test]
     fn test_field_kitchen_sink_contains() {
         let filters = basic(&[
@@ -1504,6 +2035,142 @@ mod tests {
         assert_eq!(&expected, &serde_json::json!(filters));
     }
 
+    #[test]
+    fn test_field_in_empty() {
+        let values: &[i32] = &[];
+        let filters = basic(&[field("x").in_(values)]);
+        let expected = serde_json::json!([["x", "in", []]]);
+        assert_eq!(&expected, &serde_json::json!(filters));
+    }
+
+    #[test]
+    fn test_status_in() {
+        let filters = basic(&[
+            status_in("sg_status_list", &["ip", "rev"]),
+            status_not_in("sg_status_list", &["omt"]),
+        ]);
+        let expected = serde_json::json!([
+            ["sg_status_list", "in", ["ip", "rev"]],
+            ["sg_status_list", "not_in", ["omt"]],
+        ]);
+        assert_eq!(&expected, &serde_json::json!(filters));
+    }
+
+    #[test]
+    fn test_status_categories() {
+        let categories = StatusCategories::new(&["fin", "omt"]);
+        let filters = basic(&[categories.open(), categories.closed()]);
+        let expected = serde_json::json!([
+            ["sg_status_list", "not_in", ["fin", "omt"]],
+            ["sg_status_list", "in", ["fin", "omt"]],
+        ]);
+        assert_eq!(&expected, &serde_json::json!(filters));
+
+        let filters = basic(&[categories.with_field("sg_review_status").open()]);
+        let expected = serde_json::json!([["sg_review_status", "not_in", ["fin", "omt"]]]);
+        assert_eq!(&expected, &serde_json::json!(filters));
+    }
+
+    #[test]
+    fn test_entity_ref_conversions() {
+        let asset = EntityRef::from(Entity::new("Asset", 123));
+        assert_eq!(asset.to_string(), "Asset:123");
+
+        let entity = Entity::from(&asset);
+        assert_eq!((entity.r#type.as_str(), entity.id), ("Asset", 123));
+        assert_eq!(entity.to_string(), "Asset:123");
+    }
+
+    #[test]
+    fn test_in_iter() {
+        let ids = vec![1, 2, 3, 4];
+        let filters = basic(&[
+            field("id").in_iter(ids.iter().copied().filter(|id| id % 2 == 0)),
+            field("code").in_iter(vec!["a", "b"]),
+        ]);
+        let expected = serde_json::json!([["id", "in", [2, 4]], ["code", "in", ["a", "b"]]]);
+        assert_eq!(&expected, &serde_json::json!(filters));
+    }
+
+    #[test]
+    fn test_has_thumbnail() {
+        let filters = basic(&[has_thumbnail(true), has_thumbnail(false)]);
+        let expected = serde_json::json!([["image", "is_not", null], ["image", "is", null]]);
+        assert_eq!(&expected, &serde_json::json!(filters));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_values() {
+        use chrono::{NaiveDate, TimeZone, Utc};
+
+        let due = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+        let updated = Utc.with_ymd_and_hms(2021, 3, 1, 17, 30, 0).unwrap();
+        let filters = basic(&[
+            field("due_date").is(due),
+            field("updated_at").greater_than(updated),
+            field("sg_delivery_date").is(None::<NaiveDate>),
+        ]);
+        let expected = serde_json::json!([
+            ["due_date", "is", "2021-03-01"],
+            ["updated_at", "greater_than", "2021-03-01T17:30:00Z"],
+            ["sg_delivery_date", "is", null],
+        ]);
+        assert_eq!(&expected, &serde_json::json!(filters));
+    }
+
+    #[test]
+    fn test_date_range_half_open() {
+        let filters = basic(&date_range_half_open(
+            "due_date",
+            "2021-03-01",
+            "2021-03-08",
+        ));
+        let expected = serde_json::json!([
+            ["due_date", "between", "2021-03-01", "2021-03-08"],
+            ["due_date", "is_not", "2021-03-08"],
+        ]);
+        assert_eq!(&expected, &serde_json::json!(filters));
+    }
+
+    #[test]
+    fn test_field_name_matches_glob() {
+        let cases = vec![
+            (
+                "bub*",
+                serde_json::json!([["x", "name_starts_with", "bub"]]),
+            ),
+            ("*bub", serde_json::json!([["x", "name_ends_with", "bub"]])),
+            ("*bub*", serde_json::json!([["x", "name_contains", "bub"]])),
+            (
+                "**bub***",
+                serde_json::json!([["x", "name_contains", "bub"]]),
+            ),
+            (
+                "bub*v01",
+                serde_json::json!([
+                    ["x", "name_starts_with", "bub"],
+                    ["x", "name_ends_with", "v01"]
+                ]),
+            ),
+            ("*", serde_json::json!([])),
+        ];
+        for (pattern, expected) in cases {
+            let filters = basic(&field("x").name_matches_glob(pattern).unwrap());
+            assert_eq!(&expected, &serde_json::json!(filters), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_field_name_matches_glob_unsupported() {
+        for pattern in &["bub", "bub*v*", "*bub*v01*", "bub_?", "bub_[0-9]*", ""] {
+            match field("x").name_matches_glob(pattern) {
+                Err(crate::Error::UnsupportedGlob(p)) => assert_eq!(&p, pattern),
+                other => panic!("{}: expected UnsupportedGlob, got {:?}", pattern, other),
+            }
+        }
+    }
+
     #[test]
     fn test_field_kitchen_sink_type() {
         let filters = basic(&[field("x").type_is("Asset"), field("x").type_is_not("Asset")]);
@@ -17,6 +17,7 @@
 //! > Other tls backends are available and can be selected with the features
 //! > listed below.
 //!
+//! - `brotli` (to enable brotli compression).
 //! - `gzip` (to enable gzip compression).
 //! - `native-tls` (as discussed above, uses whatever the canonical tls library
 //!    is for the platform).
@@ -24,6 +25,14 @@
 //!    library from source as a part of the crate's build script).
 //! - `rustls` (uses the [rustls crate] which is a *pure rust tls implementation*).
 //!
+//! Outside of the HTTP client, the `chrono` feature allows [chrono] dates
+//! and times to be used as filter values (see
+//! [`FieldValue`](`crate::filters::FieldValue`)).
+//!
+//! For testing code built on this crate, the `mock-transport` feature adds a
+//! way to give canned responses without an HTTP server (see the
+//! `mock_transport` module).
+//!
 //! ## Usage
 //!
 //! The general pattern of usage starts with a [`Client`].
@@ -143,6 +152,7 @@
 //! Please refer to the docs for your logger crate to see how to adjust log levels
 //! for crates and modules.
 //!
+//! [chrono]: https://crates.io/crates/chrono
 //! [native-tls crate]: https://crates.io/crates/native-tls
 //! [rustls crate]: https://crates.io/crates/rustls
 //! [shotgrid]: https://www.shotgridsoftware.com/
@@ -150,30 +160,55 @@
 //! [serde]: https://crates.io/crates/serde
 //! [serde_json]: https://crates.io/crates/serde_json
 
+use std::collections::HashMap;
 use std::env;
 use std::fs::File;
 use std::io::Read;
 #[macro_use]
 extern crate serde_derive;
-use crate::types::{ErrorObject, ErrorResponse};
+use crate::types::{ErrorObject, ErrorResponse, ServerInfo, ServerInfoResponse};
 use log::{debug, error, trace};
-use reqwest::Response;
+use mime_guess::Mime;
+use reqwest::{Proxy, RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::Arc;
+mod create;
+mod download;
+mod entity_activity_stream_read;
 mod entity_relationship_read;
 pub mod filters;
+#[cfg(feature = "mock-transport")]
+pub mod mock_transport;
+mod paginate;
+mod read_cache;
+mod retry;
+mod return_fields;
 mod schema;
 mod search;
 mod session;
 mod summarize;
 mod text_search;
 pub mod types;
+mod update;
 mod upload;
+pub use crate::create::CreateBuilder;
+pub use crate::download::FileDownload;
+pub use crate::entity_activity_stream_read::EntityActivityStreamReadReqBuilder;
 pub use crate::entity_relationship_read::EntityRelationshipReadReqBuilder;
-pub use crate::session::Session;
+pub use crate::read_cache::ReadCache;
+pub use crate::retry::RetryConfig;
+pub use crate::return_fields::{ReturnFields, ReturnFieldsTarget};
+pub use crate::session::{Login, Session};
 pub use crate::summarize::SummarizeReqBuilder;
-pub use search::SearchBuilder;
-pub use upload::{UploadReqBuilder, MAX_MULTIPART_CHUNK_SIZE, MIN_MULTIPART_CHUNK_SIZE};
+pub use crate::update::UpdateBuilder;
+pub use search::{SearchBuilder, SearchResults};
+pub use upload::{
+    MultipartCheckpoint, ThumbnailUploadReqBuilder, UploadReqBuilder, UploadResult,
+    MAX_MULTIPART_CHUNK_SIZE, MIN_MULTIPART_CHUNK_SIZE, MULTIPART_REQUIRED_SIZE,
+};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -183,22 +218,41 @@ pub mod transport {
     pub use reqwest;
 }
 
+/// The types most programs using this crate need, for importing in one go.
+///
+/// ```
+/// use shotgrid_rs::prelude::*;
+///
+/// let shots = filters::basic(&[
+///     field("project").is(EntityRef::new("Project", 123)),
+///     field("code").starts_with("bub_"),
+/// ]);
+/// ```
+pub mod prelude {
+    pub use crate::filters::{self, field, EntityRef};
+    pub use crate::types::{
+        Entity, PaginationLinks, ResourceArrayResponse, SelfLink, SummaryFieldType,
+    };
+    pub use crate::{Client, Session};
+}
+
 type HttpClient = transport::reqwest::Client;
 
+/// The `User-Agent` sent by clients created with [`Client::new()`], unless
+/// [`Client::with_user_agent()`] says otherwise.
+pub const DEFAULT_USER_AGENT: &str = concat!("shotgrid-rs/", env!("CARGO_PKG_VERSION"));
+
 /// Get a default http client with ca certs added to it if specified via env var.
-fn get_http_client() -> Result<HttpClient> {
-    let builder = HttpClient::builder();
+fn get_http_client(proxy: Option<&Proxy>) -> Result<HttpClient> {
+    let builder = HttpClient::builder().user_agent(DEFAULT_USER_AGENT);
+    let builder = match proxy {
+        Some(proxy) => builder.proxy(proxy.clone()),
+        None => builder,
+    };
 
     let builder = if let Ok(fp) = env::var("CA_BUNDLE") {
         debug!("Using ca bundle from: `{}`", fp);
-        let mut buf = Vec::new();
-        File::open(fp)
-            .map_err(|e| Error::BadClientConfig(e.to_string()))?
-            .read_to_end(&mut buf)
-            .map_err(|e| Error::BadClientConfig(e.to_string()))?;
-        let cert = transport::reqwest::Certificate::from_pem(&buf)
-            .map_err(|e| Error::BadClientConfig(e.to_string()))?;
-        builder.add_root_certificate(cert)
+        builder.add_root_certificate(read_ca_bundle(&fp)?)
     } else {
         builder
     };
@@ -206,16 +260,98 @@ fn get_http_client() -> Result<HttpClient> {
         .build()
         .map_err(|e| Error::BadClientConfig(e.to_string()))
 }
-#[derive(Clone, Debug)]
+
+/// Read the PEM encoded cert at `fp`, for use as an extra root certificate.
+fn read_ca_bundle(fp: &str) -> Result<transport::reqwest::Certificate> {
+    let mut buf = Vec::new();
+    File::open(fp)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Error::CaBundleNotFound(fp.to_string()),
+            _ => Error::CaBundleRead(e),
+        })?
+        .read_to_end(&mut buf)
+        .map_err(Error::CaBundleRead)?;
+    transport::reqwest::Certificate::from_pem(&buf).map_err(Error::CaBundleInvalid)
+}
+
+/// Make sure `sg_server` looks like the base url of a ShotGrid server, so a
+/// typo is reported up front rather than by the first request.
+fn check_server(sg_server: &str) -> Result<()> {
+    let url = reqwest::Url::parse(sg_server)
+        .map_err(|e| Error::BadClientConfig(format!("Invalid server `{}`: {}", sg_server, e)))?;
+    match url.scheme() {
+        "http" | "https" if url.has_host() => Ok(()),
+        _ => Err(Error::BadClientConfig(format!(
+            "Invalid server `{}`: expected an http or https url.",
+            sg_server
+        ))),
+    }
+}
+
+/// The path to the REST API used unless [`Client::with_api_base()`] says
+/// otherwise.
+pub const DEFAULT_API_BASE: &str = "/api/v1";
+
+/// A ShotGrid API Client.
+///
+/// Each client holds its own pool of connections to the server, so programs
+/// should create one and reuse it for all their requests rather than calling
+/// [`Client::new()`] per request. Clones share the same pool, as do clients
+/// wrapped up by [`Client::shared()`] for passing around between tasks.
+///
+/// # Timeouts
+///
+/// Any timeout set on the HTTP client (see [`Client::with_transport()`])
+/// applies to every request. Calls known to be slow, such as a large
+/// [`search`](`SearchBuilder::timeout()`),
+/// [`summarize`](`SummarizeReqBuilder::timeout()`),
+/// [`text search`](`Session::text_search()`), or reading the
+/// [`schema`](`Session::with_schema_timeout()`), can be given their own
+/// timeout instead, so the client-wide one can stay tight.
+#[derive(Clone)]
 pub struct Client {
     /// Base url for the ShotGrid server.
     sg_server: String,
-    /// HTTP Client used internally to make requests to ShotGrid.
-    http: HttpClient,
+    /// Path to the REST API, relative to `sg_server`.
+    api_base: String,
+    /// HTTP Client used internally to make requests to ShotGrid, shared by
+    /// all clones.
+    http: Arc<HttpClient>,
     /// API User (aka "script") name, used to generate API Tokens.
     script_name: Option<String>,
     /// API User (aka "script") secret key, used to generate API Tokens.
     script_key: Option<String>,
+    /// Extra file extension to mimetype mappings, consulted by uploads before
+    /// falling back to `mime_guess`.
+    mime_types: HashMap<String, Mime>,
+    /// How read-only requests are retried, if at all.
+    retry: Option<RetryConfig>,
+    /// The `User-Agent` to send with every request in place of the one the
+    /// HTTP client was built with, if any.
+    user_agent: Option<String>,
+    /// Whether the HTTP client was given to [`Client::with_transport()`],
+    /// rather than built by [`Client::new()`], and so can't be rebuilt.
+    custom_transport: bool,
+    /// Canned responses to give in place of sending requests, if any.
+    #[cfg(feature = "mock-transport")]
+    mock: Option<Arc<mock_transport::MockTransport>>,
+}
+
+// Written by hand to keep the script key out of logs.
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("sg_server", &self.sg_server)
+            .field("api_base", &self.api_base)
+            .field("http", &self.http)
+            .field("script_name", &self.script_name)
+            .field("script_key", &self.script_key.as_ref().map(|_| REDACTED))
+            .field("mime_types", &self.mime_types)
+            .field("retry", &self.retry)
+            .field("user_agent", &self.user_agent)
+            .field("custom_transport", &self.custom_transport)
+            .finish()
+    }
 }
 
 impl Client {
@@ -226,19 +362,21 @@ impl Client {
     ///
     /// This will `Err` when:
     ///
-    /// - `CA_BUNDLE` is set, but the file path it points to is invalid.
+    /// - `sg_server` isn't an `http` or `https` url, such as when the scheme is
+    ///   missing ([`Error::BadClientConfig`]).
+    /// - `CA_BUNDLE` is set, but the file it points to is missing
+    ///   ([`Error::CaBundleNotFound`]), can't be read ([`Error::CaBundleRead`]),
+    ///   or isn't a valid PEM encoded cert ([`Error::CaBundleInvalid`]).
     pub fn new(
         sg_server: String,
         script_name: Option<&str>,
         script_key: Option<&str>,
     ) -> Result<Self> {
-        let client = get_http_client()?;
-        Ok(Self {
-            sg_server,
-            http: client,
-            script_name: script_name.map(Into::into),
-            script_key: script_key.map(Into::into),
-        })
+        check_server(&sg_server)?;
+        let client = get_http_client(None)?;
+        let mut client = Self::with_transport(sg_server, script_name, script_key, client);
+        client.custom_transport = false;
+        Ok(client)
     }
 
     /// Create a new ShotGrid API Client, but configure the HTTP client yourself.
@@ -249,6 +387,10 @@ impl Client {
     /// For your convenience, the [`transport::reqwest`] module has a re-export
     /// of the entire [`reqwest`] crate so you have access to all the types
     /// required for configuring the client.
+    ///
+    /// Unlike [`Client::new()`], `sg_server` is not checked here; any trailing
+    /// slash is trimmed, but an invalid url won't be noticed until the first
+    /// request fails.
     pub fn with_transport(
         sg_server: String,
         script_name: Option<&str>,
@@ -256,26 +398,287 @@ impl Client {
         http_client: HttpClient,
     ) -> Self {
         Self {
-            sg_server,
-            http: http_client,
+            sg_server: sg_server.trim_end_matches('/').to_string(),
+            api_base: String::from(DEFAULT_API_BASE),
+            http: Arc::new(http_client),
             script_name: script_name.map(Into::into),
             script_key: script_key.map(Into::into),
+            mime_types: HashMap::new(),
+            retry: None,
+            user_agent: None,
+            custom_transport: true,
+            #[cfg(feature = "mock-transport")]
+            mock: None,
+        }
+    }
+
+    /// Create a new ShotGrid API Client which, rather than sending requests,
+    /// gives the canned responses held by `mock`.
+    ///
+    /// This is for testing code built on this crate without standing up an
+    /// HTTP server. See the [`mock_transport`] module for details.
+    #[cfg(feature = "mock-transport")]
+    pub fn with_mock_transport(
+        sg_server: String,
+        script_name: Option<&str>,
+        script_key: Option<&str>,
+        mock: mock_transport::MockTransport,
+    ) -> Self {
+        let mut client =
+            Self::with_transport(sg_server, script_name, script_key, HttpClient::new());
+        client.mock = Some(Arc::new(mock));
+        client
+    }
+
+    /// The canned responses given in place of sending requests, when created
+    /// with [`Client::with_mock_transport()`].
+    #[cfg(feature = "mock-transport")]
+    pub fn mock_transport(&self) -> Option<&mock_transport::MockTransport> {
+        self.mock.as_deref()
+    }
+
+    /// Wrap the client in an [`Arc`] so it can be handed out to many tasks,
+    /// all sharing the same connection pool.
+    ///
+    /// ```
+    /// use shotgrid_rs::Client;
+    /// use std::sync::Arc;
+    ///
+    /// let sg = Client::new(String::from("https://shotgrid.example.com"), None, None)?.shared();
+    ///
+    /// // Each worker gets a handle to the same client.
+    /// let workers: Vec<Arc<Client>> = (0..4).map(|_| Arc::clone(&sg)).collect();
+    /// # assert_eq!(Arc::strong_count(&sg), 5);
+    /// # Ok::<(), shotgrid_rs::Error>(())
+    /// ```
+    pub fn shared(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+
+    /// Identify requests made by this client with the given `User-Agent`
+    /// (defaults to [`DEFAULT_USER_AGENT`]).
+    ///
+    /// A descriptive name, such as the name of the service making requests,
+    /// makes it easier to pick out its traffic when looking into problems on
+    /// the ShotGrid side.
+    ///
+    /// The header is added to each request, so it also takes the place of any
+    /// user agent set on a client given to [`Client::with_transport()`].
+    ///
+    /// ```
+    /// use shotgrid_rs::Client;
+    ///
+    /// let sg = Client::new(String::from("https://shotgrid.example.com"), None, None)?
+    ///     .with_user_agent("asset-publisher/2.1");
+    /// # Ok::<(), shotgrid_rs::Error>(())
+    /// ```
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Send all requests through `proxy`.
+    ///
+    /// By default, the HTTP client picks up a proxy from the `HTTP_PROXY` and
+    /// `HTTPS_PROXY` environment variables, skipping any hosts listed in
+    /// `NO_PROXY`. Setting a proxy here replaces those, and `NO_PROXY` is
+    /// *not* consulted for it.
+    ///
+    /// This swaps in a new HTTP client built the same way as
+    /// [`Client::new()`] builds it. Clones made before the swap keep the old
+    /// one.
+    ///
+    /// This will `Err` when:
+    ///
+    /// - the client was created by [`Client::with_transport()`], since that
+    ///   HTTP client can't be rebuilt. Set the proxy on its builder instead
+    ///   ([`Error::BadClientConfig`]).
+    /// - `CA_BUNDLE` is set, but the file it points to is missing
+    ///   ([`Error::CaBundleNotFound`]), can't be read ([`Error::CaBundleRead`]),
+    ///   or isn't a valid PEM encoded cert ([`Error::CaBundleInvalid`]).
+    ///
+    /// ```
+    /// use shotgrid_rs::transport::reqwest::Proxy;
+    /// use shotgrid_rs::Client;
+    ///
+    /// let sg = Client::new(String::from("https://shotgrid.example.com"), None, None)?
+    ///     .with_proxy(Proxy::https("http://proxy.example.com:3128")?.basic_auth("farm", "********"))?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_proxy(mut self, proxy: Proxy) -> Result<Self> {
+        if self.custom_transport {
+            return Err(Error::BadClientConfig(String::from(
+                "Can't set a proxy on a client created with `with_transport()`",
+            )));
+        }
+        self.http = Arc::new(get_http_client(Some(&proxy))?);
+        Ok(self)
+    }
+
+    /// Send all requests through the proxy at `url`, such as
+    /// `http://proxy.example.com:3128`.
+    ///
+    /// See [`Client::with_proxy()`] for details. This will also `Err` with an
+    /// [`Error::BadClientConfig`] when `url` can't be parsed.
+    pub fn with_proxy_url(self, url: &str) -> Result<Self> {
+        let proxy = Proxy::all(url)
+            .map_err(|e| Error::BadClientConfig(format!("Invalid proxy `{}`: {}", url, e)))?;
+        self.with_proxy(proxy)
+    }
+
+    /// Override the path to the REST API (defaults to `/api/v1`).
+    ///
+    /// This is useful when ShotGrid is reached through a proxy or gateway that
+    /// mounts the API under a prefix, such as `/shotgrid/api/v1`.
+    pub fn with_api_base<S: Into<String>>(mut self, api_base: S) -> Self {
+        self.api_base = api_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Retry read-only requests which fail with a
+    /// [retryable](`Error::is_retryable()`) error, backing off between
+    /// attempts per `config`.
+    ///
+    /// Only requests which are safe to repeat are retried. This covers reading
+    /// records, relationships, schema, and searching. Requests that write
+    /// (creates, updates, deletes, uploads, etc) are never retried, since a
+    /// write that timed out on our end may still have happened on the server.
+    ///
+    /// ```
+    /// use shotgrid_rs::{Client, RetryConfig};
+    /// use std::time::Duration;
+    ///
+    /// let sg = Client::new(String::from("https://shotgrid.example.com"), None, None)?
+    ///     .with_retry(RetryConfig {
+    ///         max_retries: 5,
+    ///         base_delay: Duration::from_millis(500),
+    ///         max_delay: Duration::from_secs(30),
+    ///     });
+    /// # Ok::<(), shotgrid_rs::Error>(())
+    /// ```
+    pub fn with_retry(mut self, config: RetryConfig) -> Self {
+        self.retry = Some(config);
+        self
+    }
+
+    /// Send a read-only request, retrying it if the client is configured to.
+    pub(crate) async fn send_idempotent<D>(&self, req: RequestBuilder) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        retry::send_idempotent(self, req).await
+    }
+
+    /// Send a request, giving the raw response.
+    ///
+    /// Every request made by the client goes through here so a mock
+    /// transport, when configured, can stand in for the HTTP client.
+    pub(crate) async fn send(&self, req: RequestBuilder) -> reqwest::Result<Response> {
+        let req = match self.user_agent.as_ref() {
+            Some(user_agent) => req.header(reqwest::header::USER_AGENT, user_agent.as_str()),
+            None => req,
+        };
+        #[cfg(feature = "mock-transport")]
+        {
+            if let Some(mock) = self.mock.as_ref() {
+                return Ok(mock.handle(&req.build()?));
+            }
+        }
+        req.send().await
+    }
+
+    /// Send a read-only request with a body shaped per one of ShotGrid's
+    /// custom filter mimetypes, retrying it if the client is configured to.
+    pub(crate) async fn send_with_filter_mime<D>(
+        &self,
+        req: RequestBuilder,
+        mime: &str,
+        body: String,
+    ) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        let req = req
+            .header("Content-Type", mime)
+            // The content type is being set to ShotGrid's custom mime types
+            // to indicate the shape of the filter payload. Do not be tempted to
+            // use `.json()` here instead of `.body()` or you'll end up
+            // reverting the header set above.
+            .body(body);
+        self.send_idempotent(req).await
+    }
+
+    /// Register the mimetype to use when uploading files with the given
+    /// extension.
+    ///
+    /// Uploads guess the mimetype from the filename using the `mime_guess`
+    /// crate, which doesn't know about many VFX formats. Registered extensions
+    /// take precedence over these guesses. Extensions are matched without
+    /// regard to case, and may be given with or without a leading `.`.
+    ///
+    /// This will `Err` when the mimetype can't be parsed.
+    ///
+    /// ```
+    /// use shotgrid_rs::Client;
+    ///
+    /// let sg = Client::new(String::from("https://shotgrid.example.com"), None, None)?
+    ///     .register_mime("exr", "image/x-exr")?
+    ///     .register_mime(".abc", "application/x-alembic")?;
+    /// # Ok::<(), shotgrid_rs::Error>(())
+    /// ```
+    pub fn register_mime(mut self, extension: &str, mimetype: &str) -> Result<Self> {
+        let mimetype: Mime = mimetype
+            .parse()
+            .map_err(|_| Error::BadClientConfig(format!("Invalid mimetype: `{}`", mimetype)))?;
+        self.mime_types
+            .insert(extension.trim_start_matches('.').to_lowercase(), mimetype);
+        Ok(self)
+    }
+
+    /// Guess the mimetype for a file, preferring extensions registered with
+    /// [`Client::register_mime()`].
+    pub(crate) fn guess_mime(&self, filename: &str) -> Option<Mime> {
+        std::path::Path::new(filename)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| self.mime_types.get(&ext.to_lowercase()))
+            .cloned()
+            .or_else(|| mime_guess::from_path(filename).first())
+    }
+
+    /// Build a full url from a path given in a server response.
+    ///
+    /// ShotGrid hands out links rooted at the default api base, so these are
+    /// re-rooted at the configured one.
+    pub(crate) fn server_link(&self, link: &str) -> String {
+        match link.strip_prefix(DEFAULT_API_BASE) {
+            // Only whole path segments, so `/api/v10/...` is left alone.
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+                format!("{}{}{}", self.sg_server, self.api_base, rest)
+            }
+            _ => format!("{}{}", self.sg_server, link),
         }
     }
 
     /// Handles running authentication requests.
     async fn authenticate(&self, form_data: &[(&str, &str)]) -> Result<TokenResponse> {
-        let resp = self
+        let req = self
             .http
-            .post(&format!("{}/api/v1/auth/access_token", self.sg_server))
+            .post(&format!(
+                "{}{}/auth/access_token",
+                self.sg_server, self.api_base
+            ))
             .form(form_data)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
-        handle_response(resp).await
+            .header("Accept", "application/json");
+        handle_response(self.send(req).await?).await
     }
 
     /// Run a credential (human user logging in) challenge.
+    ///
+    /// To keep a user logged in across restarts without holding on to their
+    /// password, persist the tokens from [`Session::current_tokens()`] along
+    /// with the [`Session::login()`], and later hand both to
+    /// [`Client::reauthenticate()`].
     pub async fn authenticate_user(&self, username: &str, password: &str) -> Result<Session<'_>> {
         Ok(Session::new(
             self,
@@ -285,7 +688,8 @@ impl Client {
                 ("password", password),
             ])
             .await?,
-        ))
+        )
+        .with_login(Login::HumanUser(username.to_string())))
     }
 
     /// Get an access token payload for a given Api User aka "script."
@@ -304,7 +708,8 @@ impl Client {
                     ("client_secret", script_key),
                 ])
                 .await?,
-            ))
+            )
+            .with_login(Login::ApiUser(script_name.clone())))
         } else {
             Err(Error::BadClientConfig("Missing script name or key.".into()))
         }
@@ -328,12 +733,73 @@ impl Client {
                     ("scope", &format!("sudo_as_login:{}", login)),
                 ])
                 .await?,
-            ))
+            )
+            .with_login(Login::HumanUser(login.to_string())))
         } else {
             Err(Error::BadClientConfig("Missing script name or key.".into()))
         }
     }
 
+    /// Exchange a refresh token saved from an earlier session for a new
+    /// [`Session`].
+    ///
+    /// The refresh token *can only be used once*, so after this call it's
+    /// spent. Persist the new pair from [`Session::current_tokens()`] in its
+    /// place, otherwise the next attempt to resume will be denied.
+    ///
+    /// As with [`Client::session_from_tokens()`], `login` should be the
+    /// [`Session::login()`] of the session the refresh token came from.
+    pub async fn reauthenticate(
+        &self,
+        refresh_token: &str,
+        login: Option<Login>,
+    ) -> Result<Session<'_>> {
+        let session = Session::new(
+            self,
+            self.authenticate(&[("grant_type", "refresh"), ("refresh_token", refresh_token)])
+                .await?,
+        );
+        Ok(match login {
+            Some(login) => session.with_login(login),
+            None => session,
+        })
+    }
+
+    /// Build a [`Session`] from tokens acquired previously, skipping the
+    /// authentication request.
+    ///
+    /// This is useful for short-lived processes that persist their tokens
+    /// between runs rather than authenticating each time.
+    ///
+    /// `last_refresh` should be the unix timestamp (in seconds) of when the
+    /// tokens were acquired so the session can tell when the access token is
+    /// due to expire. When `None`, the current time is used, which is only
+    /// correct if the tokens are brand new.
+    ///
+    /// Keep in mind the refresh token *can only be used once*. If the session
+    /// ends up refreshing, any copy of the old tokens you have persisted is
+    /// spent and should be replaced.
+    ///
+    /// `login` should be the [`Session::login()`] of the session the tokens
+    /// came from. Without it, the session can't look up its
+    /// [`Session::current_user()`], and can't tell whether it acts as an
+    /// `ApiUser`.
+    pub fn session_from_tokens(
+        &self,
+        tokens: TokenResponse,
+        last_refresh: Option<u64>,
+        login: Option<Login>,
+    ) -> Session<'_> {
+        let session = match last_refresh {
+            Some(last_refresh) => Session::with_last_refresh(self, tokens, last_refresh),
+            None => Session::new(self, tokens),
+        };
+        match login {
+            Some(login) => session.with_login(login),
+            None => session,
+        }
+    }
+
     /// Provides version information about the ShotGrid server.
     ///
     /// Does not require authentication
@@ -343,19 +809,57 @@ impl Client {
     {
         let req = self
             .http
-            .get(&format!("{}/api/v1/", self.sg_server))
+            .get(&format!("{}{}/", self.sg_server, self.api_base))
             .header("Accept", "application/json");
 
-        handle_response(req.send().await?).await
+        self.send_idempotent(req).await
+    }
+
+    /// Provides version information about the ShotGrid server as a
+    /// [`ServerInfo`](`crate::types::ServerInfo`).
+    ///
+    /// Does not require authentication
+    pub async fn server_info(&self) -> Result<ServerInfo> {
+        let resp: ServerInfoResponse = self.info().await?;
+        resp.data
+            .ok_or_else(|| Error::Unexpected(String::from("Server info response missing data.")))
     }
 }
 
-/// Checks to see if the `Value` is an object with a top level "errors" key.
-fn contains_errors(value: &Value) -> bool {
-    value
-        .as_object()
-        .map(|obj| obj.contains_key("errors"))
-        .unwrap_or(false)
+/// Pulls the error objects out of a response body, if there are any, as an
+/// `ErrorResponse` shaped `Value`.
+///
+/// ShotGrid normally reports errors as an object with a top level "errors"
+/// key (with or without a null "data" alongside it). Bodies where every item
+/// of a top level array carries its own "errors" are treated the same, with
+/// all of their errors combined.
+///
+/// A null or empty "errors" is taken to mean there were none.
+fn find_errors(value: &Value) -> Option<Value> {
+    fn errors_of(value: &Value) -> Option<&Vec<Value>> {
+        value
+            .get("errors")
+            .and_then(Value::as_array)
+            .filter(|errors| !errors.is_empty())
+    }
+
+    let errors: Vec<Value> = match value {
+        Value::Object(obj) => match obj.get("errors") {
+            None | Some(Value::Null) => return None,
+            // Not an array, so let it fail to parse as an `ErrorResponse`.
+            Some(errors) if !errors.is_array() => return Some(value.clone()),
+            Some(_) => errors_of(value)?.clone(),
+        },
+        Value::Array(items) if !items.is_empty() => {
+            let mut errors = vec![];
+            for item in items {
+                errors.extend(errors_of(item)?.iter().cloned());
+            }
+            errors
+        }
+        _ => return None,
+    };
+    Some(serde_json::json!({ "errors": errors }))
 }
 
 /// Converts a response body from ShotGrid into something more meaningful.
@@ -375,12 +879,39 @@ async fn handle_response<D>(resp: Response) -> Result<D>
 where
     D: DeserializeOwned,
 {
+    // Rejected tokens are worth singling out since the caller may be able to
+    // do something about them, ie. by authenticating again.
+    // The error details are kept, since they tell an expired token apart from
+    // an invalid one.
+    if resp.status() == StatusCode::UNAUTHORIZED {
+        let source = resp.error_for_status_ref().unwrap_err();
+        let bytes = resp.bytes().await?;
+        trace!("Unauthorized: `{}`", String::from_utf8_lossy(&bytes));
+        let errors = serde_json::from_slice::<Value>(&bytes)
+            .ok()
+            .and_then(|v| find_errors(&v))
+            .and_then(|errors| serde_json::from_value::<ErrorResponse>(errors).ok())
+            .map(|resp| resp.errors)
+            .unwrap_or_default();
+        return Err(Error::Unauthorized { source, errors });
+    }
+
+    let status = resp.status();
     let bytes = resp.bytes().await?;
+    // Some successful requests give no content at all (ie. 204). These are
+    // read as `null`, so `()`, `Option` or `Value` can be asked for in place
+    // of a response body.
+    if bytes.is_empty() && status.is_success() {
+        return D::deserialize(Value::Null).map_err(|source| Error::DeserializationFailed {
+            source,
+            body: String::new(),
+        });
+    }
     // There are three (3) potential failure modes here:
     //
     // 1. Connection problems could lead to partial/garbled/non-json payload
     //    resulting in a json parse error. There could also just be no payload
-    //    for a response, ie 204.
+    //    for an error response.
     // 2. The payload could be json, but contain an error message from ShotGrid
     //    about the filter.
     // 3. The payload might parse as valid json, but the json might not fit the
@@ -393,10 +924,10 @@ where
             Err(Error::from(e))
         }
         Ok(v) => {
-            if contains_errors(&v) {
+            if let Some(errors) = find_errors(&v) {
                 trace!("Got error response from ShotGrid:\n{}", &v.to_string());
                 // case 2 - server response has error feedback.
-                match serde_json::from_value::<ErrorResponse>(v) {
+                match serde_json::from_value::<ErrorResponse>(errors) {
                     Ok(resp) => {
                         let maybe_not_found = resp
                             .errors
@@ -415,31 +946,108 @@ where
                 }
             } else {
                 // case 3 - either we get the shape we want or we get an error
-                serde_json::from_value::<D>(v).map_err(Error::from)
+                deserialize_value(&v)
             }
         }
     }
 }
 
+/// Deserialize a response body already parsed as json, keeping a (truncated)
+/// copy of the body in the error when it doesn't fit `D`.
+pub(crate) fn deserialize_value<D>(v: &Value) -> Result<D>
+where
+    D: DeserializeOwned,
+{
+    D::deserialize(v).map_err(|source| Error::DeserializationFailed {
+        source,
+        body: truncate_body(v.to_string()),
+    })
+}
+
+/// The most bytes of a response body to keep in an
+/// [`Error::DeserializationFailed`].
+const MAX_ERROR_BODY_LEN: usize = 4096;
+
+fn truncate_body(mut body: String) -> String {
+    if body.len() > MAX_ERROR_BODY_LEN {
+        let mut end = MAX_ERROR_BODY_LEN;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+        body.push_str("...");
+    }
+    body
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Client Configuration Error: `{0}`.")]
     BadClientConfig(String),
 
-    #[error("Invalid Filters: expected `filters` key to be array or object; was neither.")]
-    InvalidFilters,
+    /// The file named by the `CA_BUNDLE` environment variable doesn't exist.
+    #[error("CA Bundle Not Found - `{0}`")]
+    CaBundleNotFound(String),
+
+    /// The file named by the `CA_BUNDLE` environment variable exists, but
+    /// couldn't be read.
+    #[error("CA Bundle Read Failed - `{0}`")]
+    CaBundleRead(#[source] std::io::Error),
+
+    /// The file named by the `CA_BUNDLE` environment variable isn't a valid
+    /// PEM encoded certificate.
+    #[error("CA Bundle Invalid - `{0}`")]
+    CaBundleInvalid(#[source] reqwest::Error),
+
+    #[error("Invalid Filters: `{0}`.")]
+    InvalidFilters(String),
+
+    /// A page number or size which ShotGrid would refuse, caught before
+    /// sending the request.
+    #[error("Invalid Pagination: `{0}`.")]
+    InvalidPagination(String),
 
     #[error("Client Error: `{0}`.")]
-    ClientError(#[from] reqwest::Error),
+    ClientError(#[source] reqwest::Error),
+
+    /// The request timed out, including timeouts while connecting.
+    #[error("Timeout - `{0}`")]
+    Timeout(#[source] reqwest::Error),
+
+    /// The connection to the server could not be made, for example due to a
+    /// DNS failure or the connection being refused.
+    #[error("Connection Failed - `{0}`")]
+    Connect(#[source] reqwest::Error),
 
     #[error("JSON Parse Error: `{0}`.")]
     JsonParse(#[from] serde_json::Error),
 
+    /// The response was valid JSON, but didn't fit the type it was being
+    /// deserialized into.
+    ///
+    /// The body is left out of the error message to keep record data out of
+    /// logs, but is available here to compare against the target type.
+    #[error("Deserialization Failed: `{source}`.")]
+    DeserializationFailed {
+        source: serde_json::Error,
+        /// The response body, capped at 4KiB.
+        body: String,
+    },
+
     #[error("Entity Not Found - `{0}`")]
     NotFound(String),
 
-    #[error("Authentication Failed - `{0}`")]
-    Unauthorized(#[source] reqwest::Error),
+    #[error("Conflict - `{0}`")]
+    Conflict(String),
+
+    /// The server rejected the request's credentials (status 401).
+    #[error("Authentication Failed - `{source}`")]
+    Unauthorized {
+        source: reqwest::Error,
+        /// The error details given by ShotGrid, such as "Token Expired", if
+        /// any.
+        errors: Vec<ErrorObject>,
+    },
 
     #[error(transparent)]
     IOError(#[from] std::io::Error),
@@ -450,15 +1058,80 @@ pub enum Error {
     #[error("Server Error - `{0:?}`")]
     ServerError(Vec<ErrorObject>),
 
+    /// A server error from a search run with
+    /// [`SearchBuilder::debug_filters()`] enabled.
+    #[error("Filters Rejected - `{errors:?}` - filters: `{filters}`")]
+    FiltersRejected {
+        errors: Vec<ErrorObject>,
+        /// The filter payload, as sent to ShotGrid.
+        filters: String,
+    },
+
     #[error("Multipart uploads not supported by storage service.")]
     MultipartNotSupported,
 
+    /// A [resumable](`UploadReqBuilder::resumable()`) multipart upload failed
+    /// part way through. It can be picked up again from `checkpoint` with
+    /// [`UploadReqBuilder::resume_from()`].
+    #[error("Multipart Upload Interrupted after {} parts - `{source}`", .checkpoint.etags.len())]
+    MultipartInterrupted {
+        checkpoint: Box<MultipartCheckpoint>,
+        source: Box<Error>,
+    },
+
     #[error("File upload failed - `{0}`")]
     UploadError(String),
+
+    /// A glob pattern given to
+    /// [`Field::name_matches_glob()`](`crate::filters::Field::name_matches_glob()`)
+    /// that can't be expressed with ShotGrid's filter operators.
+    #[error("Unsupported Glob Pattern - `{0}`")]
+    UnsupportedGlob(String),
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        // Connect timeouts report as both, but are more usefully thought of
+        // as timeouts.
+        if e.is_timeout() {
+            Error::Timeout(e)
+        } else if e.is_connect() {
+            Error::Connect(e)
+        } else {
+            Error::ClientError(e)
+        }
+    }
+}
+
+impl Error {
+    /// Whether the request that produced this error might succeed if it's
+    /// sent again later.
+    ///
+    /// This is the case for timeouts, connection failures, and responses
+    /// indicating the server is overloaded or temporarily unavailable (429,
+    /// 500, 502, 503, and 504).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Timeout(_) | Error::Connect(_) => true,
+            Error::ClientError(e) => {
+                matches!(e.status(), Some(status) if retry::is_retryable_status(status))
+            }
+            Error::ServerError(errors) => errors.iter().any(|ErrorObject { status, .. }| {
+                let status = status
+                    .and_then(|status| u16::try_from(status).ok())
+                    .and_then(|status| StatusCode::from_u16(status).ok());
+                matches!(status, Some(status) if retry::is_retryable_status(status))
+            }),
+            _ => false,
+        }
+    }
 }
 
 /// Response from ShotGrid after a successful auth challenge.
-#[derive(Clone, Debug, Deserialize)]
+///
+/// The `Debug` output leaves out the tokens themselves so they don't end up
+/// in logs.
+#[derive(Clone, Deserialize, Serialize)]
 pub struct TokenResponse {
     pub token_type: String,
     pub access_token: String,
@@ -466,6 +1139,20 @@ pub struct TokenResponse {
     pub refresh_token: String,
 }
 
+impl fmt::Debug for TokenResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenResponse")
+            .field("token_type", &self.token_type)
+            .field("access_token", &REDACTED)
+            .field("expires_in", &self.expires_in)
+            .field("refresh_token", &REDACTED)
+            .finish()
+    }
+}
+
+/// Stands in for secrets in `Debug` output.
+const REDACTED: &str = "***";
+
 #[cfg(doctest)]
 mod readme_tests {
     use doc_comment::doctest;
@@ -478,6 +1165,48 @@ mod mock_tests {
     use wiremock::matchers::{method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
+    #[test]
+    fn test_clones_share_http_client() {
+        let sg = Client::new(String::from("https://shotgrid.example.com"), None, None).unwrap();
+        let clone = sg.clone().with_api_base("/shotgrid/api/v1");
+        assert!(Arc::ptr_eq(&sg.http, &clone.http));
+
+        let custom = Client::with_transport(
+            String::from("https://shotgrid.example.com"),
+            None,
+            None,
+            HttpClient::new(),
+        );
+        assert!(Arc::ptr_eq(&custom.http, &custom.clone().http));
+        assert!(!Arc::ptr_eq(&sg.http, &custom.http));
+        // Setting the user agent keeps the given client.
+        let renamed = custom.clone().with_user_agent("asset-publisher/2.1");
+        assert!(Arc::ptr_eq(&custom.http, &renamed.http));
+    }
+
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let sg = Client::new(
+            String::from("https://shotgrid.example.com"),
+            Some("my-api-user"),
+            Some("$$SCRIPT_KEY$$"),
+        )
+        .unwrap();
+        let debug = format!("{:?}", sg);
+        assert!(debug.contains("my-api-user"), "{}", debug);
+        assert!(!debug.contains("$$SCRIPT_KEY$$"), "{}", debug);
+
+        let tokens = TokenResponse {
+            token_type: String::from("Bearer"),
+            access_token: String::from("$$ACCESS_TOKEN$$"),
+            expires_in: 600,
+            refresh_token: String::from("$$REFRESH_TOKEN$$"),
+        };
+        let debug = format!("{:?}", tokens);
+        assert!(!debug.contains("$$ACCESS_TOKEN$$"), "{}", debug);
+        assert!(!debug.contains("$$REFRESH_TOKEN$$"), "{}", debug);
+    }
+
     #[tokio::test]
     async fn test_login_good_creds() {
         let mock_server = MockServer::start().await;
@@ -547,4 +1276,470 @@ mod mock_tests {
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_server_info() {
+        let mock_server = MockServer::start().await;
+        let body = r##"
+        {
+          "data": {
+            "shotgun_version": "v8.16.0.0-a1b2c3",
+            "api_version": "v1.x",
+            "version": [8, 16, 0]
+          },
+          "links": { "self": "/api/v1/" }
+        }
+        "##;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let info = sg.server_info().await.unwrap();
+        assert_eq!(info.version, Some(vec![8, 16, 0]));
+        assert_eq!(info.shotgun_version.as_deref(), Some("v8.16.0.0-a1b2c3"));
+        assert_eq!(info.api_version.as_deref(), Some("v1.x"));
+        assert!(info.version_at_least(&[8, 16]));
+    }
+
+    #[tokio::test]
+    async fn test_api_base_override() {
+        let mock_server = MockServer::start().await;
+        let body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/shotgrid/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let sg = Client::new(mock_server.uri(), None, None)
+            .unwrap()
+            .with_api_base("/shotgrid/api/v1/");
+
+        let _sess = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            format!(
+                "{}/shotgrid/api/v1/entity/notes/1/_upload",
+                mock_server.uri()
+            ),
+            sg.server_link("/api/v1/entity/notes/1/_upload")
+        );
+        assert_eq!(
+            format!("{}/shotgrid/api/v1", mock_server.uri()),
+            sg.server_link("/api/v1")
+        );
+        assert_eq!(
+            format!("{}/api/v10/entity/notes/1", mock_server.uri()),
+            sg.server_link("/api/v10/entity/notes/1")
+        );
+    }
+
+    #[test]
+    fn test_read_ca_bundle() {
+        let dir = std::env::temp_dir().join("shotgrid-rs-test-read-ca-bundle");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let missing = dir.join("missing.pem");
+        assert!(matches!(
+            read_ca_bundle(missing.to_str().unwrap()),
+            Err(Error::CaBundleNotFound(path)) if path == missing.to_str().unwrap()
+        ));
+
+        // Directories open fine, but can't be read from.
+        assert!(matches!(
+            read_ca_bundle(dir.to_str().unwrap()),
+            Err(Error::CaBundleRead(_))
+        ));
+
+        #[cfg(feature = "native-tls")]
+        {
+            let garbage = dir.join("garbage.pem");
+            std::fs::write(&garbage, "not a cert").unwrap();
+            assert!(matches!(
+                read_ca_bundle(garbage.to_str().unwrap()),
+                Err(Error::CaBundleInvalid(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_server_trailing_slash_trimmed() {
+        let sg = Client::new(String::from("https://shotgrid.example.com/"), None, None).unwrap();
+        assert_eq!(sg.sg_server, "https://shotgrid.example.com");
+        assert_eq!(
+            sg.server_link("/api/v1/entity/Shot/123"),
+            "https://shotgrid.example.com/api/v1/entity/Shot/123"
+        );
+
+        let custom = Client::with_transport(
+            String::from("https://shotgrid.example.com//"),
+            None,
+            None,
+            HttpClient::new(),
+        );
+        assert_eq!(custom.sg_server, "https://shotgrid.example.com");
+    }
+
+    #[test]
+    fn test_invalid_server_is_err() {
+        for server in &["shotgrid.example.com", "", "ftp://shotgrid.example.com"] {
+            assert!(
+                matches!(
+                    Client::new(server.to_string(), None, None),
+                    Err(Error::BadClientConfig(_))
+                ),
+                "{}",
+                server
+            );
+        }
+        assert!(Client::new(String::from("http://localhost:8080"), None, None).is_ok());
+    }
+
+    #[test]
+    fn test_register_mime() {
+        let sg = Client::new(String::from("https://shotgrid.example.com"), None, None)
+            .unwrap()
+            .register_mime(".exr", "image/x-exr")
+            .unwrap();
+
+        let guess = |filename| sg.guess_mime(filename).map(|m| m.to_string());
+        assert_eq!(guess("beauty.1001.exr").as_deref(), Some("image/x-exr"));
+        assert_eq!(guess("BEAUTY.1001.EXR").as_deref(), Some("image/x-exr"));
+        assert_eq!(guess("poster.png").as_deref(), Some("image/png"));
+
+        assert!(matches!(
+            sg.clone().register_mime("abc", "not a mimetype"),
+            Err(Error::BadClientConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retry_reads_only() {
+        let mock_server = MockServer::start().await;
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+        let unavailable_body = r##"
+        {
+          "errors": [
+            {
+              "id": "xxxxx",
+              "status": 503,
+              "code": 100,
+              "title": "Service Unavailable",
+              "detail": null,
+              "source": null,
+              "meta": null
+            }
+          ]
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/1001"))
+            .respond_with(
+                ResponseTemplate::new(503).set_body_raw(unavailable_body, "application/json"),
+            )
+            // The initial attempt, plus 2 retries.
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot"))
+            .respond_with(
+                ResponseTemplate::new(503).set_body_raw(unavailable_body, "application/json"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None)
+            .unwrap()
+            .with_retry(RetryConfig {
+                max_retries: 2,
+                base_delay: std::time::Duration::from_millis(1),
+                max_delay: std::time::Duration::from_millis(5),
+            });
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let err = session.read::<Value>("Shot", 1001, None).await.unwrap_err();
+        assert!(err.is_retryable());
+
+        let err = session
+            .create::<_, Value>("Shot", &serde_json::json!({"code": "sh010"}), None)
+            .await
+            .unwrap_err();
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_send_with_filter_mime_keeps_content_type() {
+        use wiremock::matchers::header;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot/_search"))
+            .and(header("Content-Type", filters::MIME_FILTER_HASH))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("{}", "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let req = sg
+            .http
+            .post(&format!("{}/api/v1/entity/Shot/_search", mock_server.uri()));
+        let body = r#"{"filters": {"logical_operator": "and", "conditions": []}}"#;
+        let _: Value = sg
+            .send_with_filter_mime(req, filters::MIME_FILTER_HASH, body.to_string())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_user_agent() {
+        use wiremock::matchers::header;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/"))
+            .and(header("User-Agent", DEFAULT_USER_AGENT))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("{}", "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/"))
+            .and(header("User-Agent", "asset-publisher/2.1"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("{}", "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let _: Value = sg.info().await.unwrap();
+        let sg = sg.with_user_agent("asset-publisher/2.1");
+        let _: Value = sg.info().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_proxy() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("{}", "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // Nothing resolves `.invalid`, so the request can only land on the
+        // mock server by way of the proxy.
+        let sg = Client::new(String::from("http://shotgrid.invalid"), None, None)
+            .unwrap()
+            .with_user_agent("asset-publisher/2.1")
+            .with_proxy_url(&mock_server.uri())
+            .unwrap();
+        assert_eq!(sg.user_agent.as_deref(), Some("asset-publisher/2.1"));
+        let _: Value = sg.info().await.unwrap();
+
+        assert!(matches!(
+            Client::new(String::from("https://shotgrid.example.com"), None, None)
+                .unwrap()
+                .with_proxy_url("not a url"),
+            Err(Error::BadClientConfig(_))
+        ));
+        assert!(matches!(
+            Client::with_transport(
+                String::from("https://shotgrid.example.com"),
+                None,
+                None,
+                HttpClient::new(),
+            )
+            .with_proxy_url(&mock_server.uri()),
+            Err(Error::BadClientConfig(_))
+        ));
+    }
+
+    #[cfg(feature = "brotli")]
+    #[tokio::test]
+    async fn test_brotli_response_decoded() {
+        // `{"data": {"id": 1001, "type": "Shot"}}`, brotli encoded.
+        let body: &[u8] = &[
+            139, 18, 128, 123, 34, 100, 97, 116, 97, 34, 58, 32, 123, 34, 105, 100, 34, 58, 32, 49,
+            48, 48, 49, 44, 32, 34, 116, 121, 112, 101, 34, 58, 32, 34, 83, 104, 111, 116, 34, 125,
+            125, 3,
+        ];
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Encoding", "br")
+                    .set_body_raw(body, "application/json"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let resp: Value = sg.info().await.unwrap();
+        assert_eq!(resp["data"]["id"], 1001);
+    }
+
+    #[tokio::test]
+    async fn test_deserialization_failure_keeps_body() {
+        #[derive(Debug, serde_derive::Deserialize)]
+        struct Info {
+            #[allow(dead_code)]
+            version: String,
+        }
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(r#"{"version": [8, 0]}"#, "application/json"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        match sg.info::<Info>().await {
+            Err(Error::DeserializationFailed { body, .. }) => {
+                assert!(body.contains(r#""version":[8,0]"#), "{}", body);
+            }
+            other => panic!("Expected Error::DeserializationFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_find_errors() {
+        use serde_json::json;
+
+        let error = json!({"status": 400, "code": 103, "title": "Invalid filters"});
+
+        assert_eq!(
+            find_errors(&json!({"data": null, "errors": [error]})),
+            Some(json!({"errors": [error]}))
+        );
+        assert_eq!(
+            find_errors(&json!([{"errors": [error]}, {"errors": [error]}])),
+            Some(json!({"errors": [error, error]}))
+        );
+
+        // Arrays of records, or records alongside empty errors, are fine.
+        assert_eq!(find_errors(&json!([{"id": 1}, {"errors": [error]}])), None);
+        assert_eq!(find_errors(&json!([])), None);
+        assert_eq!(find_errors(&json!({"data": [], "errors": null})), None);
+        assert_eq!(find_errors(&json!({"data": [], "errors": []})), None);
+        assert_eq!(find_errors(&json!("errors")), None);
+    }
+
+    #[tokio::test]
+    async fn test_array_wrapped_errors() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/"))
+            .respond_with(ResponseTemplate::new(400).set_body_raw(
+                r#"[{"errors": [{"status": 400, "code": 103, "title": "Bad"}]}]"#,
+                "application/json",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        match sg.info::<Value>().await {
+            Err(Error::ServerError(errors)) => assert_eq!(errors[0].code, Some(103)),
+            other => panic!("Expected Error::ServerError, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_response_body() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        sg.info::<()>().await.unwrap();
+        assert!(sg.info::<Option<ServerInfo>>().await.unwrap().is_none());
+        match sg.info::<ServerInfoResponse>().await {
+            Err(Error::DeserializationFailed { body, .. }) => assert!(body.is_empty()),
+            other => panic!("Expected Error::DeserializationFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_truncate_body() {
+        assert_eq!(truncate_body(String::from("{}")), "{}");
+
+        let body = truncate_body("é".repeat(MAX_ERROR_BODY_LEN));
+        assert_eq!(body.len(), MAX_ERROR_BODY_LEN + "...".len());
+        assert!(body.ends_with("..."));
+    }
+
+    #[tokio::test]
+    async fn test_connect_error_classified() {
+        // Grab a free port, then let it go so nothing is listening on it.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let sg = Client::new(format!("http://127.0.0.1:{}", port), None, None).unwrap();
+        let err = sg.info::<Value>().await.unwrap_err();
+        assert!(matches!(err, Error::Connect(_)), "{:?}", err);
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_timeout_error_classified() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/"))
+            .respond_with(
+                ResponseTemplate::new(200).set_delay(std::time::Duration::from_millis(500)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let http = HttpClient::builder()
+            .timeout(std::time::Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let sg = Client::with_transport(mock_server.uri(), None, None, http);
+        let err = sg.info::<Value>().await.unwrap_err();
+        assert!(matches!(err, Error::Timeout(_)), "{:?}", err);
+    }
 }
@@ -0,0 +1,177 @@
+//! Canned responses for testing code built on this crate, without an HTTP
+//! server.
+//!
+//! Requires the `mock-transport` feature.
+//!
+//! A [`MockTransport`] maps a method and path to the response to give for
+//! it. A [`Client`](`crate::Client`) created with
+//! [`Client::with_mock_transport()`](`crate::Client::with_mock_transport()`)
+//! looks up every request it would have sent in the mapping instead, so tests
+//! can run entirely in-process.
+//!
+//! ```
+//! # #[tokio::main]
+//! # async fn main() -> shotgrid_rs::Result<()> {
+//! use shotgrid_rs::mock_transport::MockTransport;
+//! use shotgrid_rs::transport::reqwest::Method;
+//! use shotgrid_rs::Client;
+//! use serde_json::Value;
+//!
+//! let mock = MockTransport::new()
+//!     .respond(
+//!         Method::POST,
+//!         "/api/v1/auth/access_token",
+//!         200,
+//!         r#"{"token_type": "Bearer", "access_token": "a", "expires_in": 600, "refresh_token": "r"}"#,
+//!     )
+//!     .respond(
+//!         Method::GET,
+//!         "/api/v1/entity/Shot/123",
+//!         200,
+//!         r#"{"data": {"id": 123, "type": "Shot", "attributes": {"code": "sh010"}}}"#,
+//!     );
+//!
+//! let sg = Client::with_mock_transport(String::from("https://shotgrid.example.com"), None, None, mock);
+//! let session = sg.authenticate_user("nbabcock", "iCdEAD!ppl").await?;
+//! let shot: Value = session.read("Shot", 123, Some("code")).await?;
+//! assert_eq!(shot["data"]["attributes"]["code"], "sh010");
+//! # Ok(())
+//! # }
+//! ```
+use reqwest::{Method, Request, Response, StatusCode};
+use std::sync::Mutex;
+
+#[derive(Debug)]
+struct MockRoute {
+    method: Method,
+    path: String,
+    status: StatusCode,
+    body: String,
+}
+
+/// A mapping of requests to canned responses.
+///
+/// Requests are matched on their method and url path, ignoring the query
+/// string. When more than one response is registered for the same request,
+/// the first one registered is given. Requests with no response registered
+/// get a `404` with a ShotGrid style error body, which surfaces as an
+/// [`Error::NotFound`](`crate::Error::NotFound`).
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    routes: Vec<MockRoute>,
+    received: Mutex<Vec<(Method, String)>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give a response with the given status and (JSON) body to requests
+    /// with the given method and path, such as `/api/v1/entity/Shot/123`.
+    pub fn respond<S: Into<String>>(
+        mut self,
+        method: Method,
+        path: &str,
+        status: u16,
+        body: S,
+    ) -> Self {
+        self.routes.push(MockRoute {
+            method,
+            path: path.to_string(),
+            status: StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            body: body.into(),
+        });
+        self
+    }
+
+    /// The method and path of every request handled so far, in the order
+    /// they were made.
+    pub fn received(&self) -> Vec<(Method, String)> {
+        self.received
+            .lock()
+            .map(|received| received.clone())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn handle(&self, req: &Request) -> Response {
+        let path = req.url().path();
+        if let Ok(mut received) = self.received.lock() {
+            received.push((req.method().clone(), path.to_string()));
+        }
+
+        let (status, body) = match self
+            .routes
+            .iter()
+            .find(|route| route.method == *req.method() && route.path == path)
+        {
+            Some(route) => (route.status, route.body.clone()),
+            None => (
+                StatusCode::NOT_FOUND,
+                serde_json::json!({
+                    "errors": [{
+                        "status": 404,
+                        "title": "No mock response",
+                        "detail": format!("No response registered for `{} {}`.", req.method(), path),
+                    }]
+                })
+                .to_string(),
+            ),
+        };
+
+        let mut resp = http::Response::new(body);
+        *resp.status_mut() = status;
+        resp.headers_mut().insert(
+            reqwest::header::CONTENT_TYPE,
+            reqwest::header::HeaderValue::from_static("application/json"),
+        );
+        Response::from(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Client, Error};
+    use serde_json::Value;
+
+    #[tokio::test]
+    async fn test_mock_transport() {
+        let mock = MockTransport::new()
+            .respond(
+                Method::POST,
+                "/api/v1/auth/access_token",
+                200,
+                r#"{"token_type": "Bearer", "access_token": "a", "expires_in": 600, "refresh_token": "r"}"#,
+            )
+            .respond(
+                Method::GET,
+                "/api/v1/entity/Shot/123",
+                200,
+                r#"{"data": {"id": 123, "type": "Shot"}}"#,
+            );
+        let sg = Client::with_mock_transport(
+            String::from("https://shotgrid.example.com"),
+            None,
+            None,
+            mock,
+        );
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let shot: Value = session.read("Shot", 123, None).await.unwrap();
+        assert_eq!(shot["data"]["id"], 123);
+
+        let err = session.read::<Value>("Shot", 456, None).await.unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)), "{:?}", err);
+
+        let received = sg.mock_transport().unwrap().received();
+        assert_eq!(received.len(), 3);
+        assert_eq!(
+            received[2],
+            (Method::GET, String::from("/api/v1/entity/Shot/456"))
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! Walking the pages of list-style responses.
+use crate::types::{PaginationLinks, ResourceArrayResponse};
+use crate::Result;
+use futures::future::{self, Future};
+use futures::stream::{self, Stream, StreamExt};
+
+/// A stream of the records from each page, starting with `first_page`, for
+/// as long as ShotGrid gives a link to the next one.
+///
+/// `fetch` reads the page with the given number. The stream ends after the
+/// first error.
+pub(crate) fn paginate<'a, R, F, Fut>(
+    first_page: usize,
+    fetch: F,
+) -> impl Stream<Item = Result<R>> + 'a
+where
+    R: 'a,
+    F: Fn(usize) -> Fut + 'a,
+    Fut: Future<Output = Result<ResourceArrayResponse<R, PaginationLinks>>> + 'a,
+{
+    stream::unfold(Some(first_page), move |page| {
+        let fetched = page.map(|number| (number, fetch(number)));
+        async move {
+            let (number, fut) = fetched?;
+            match fut.await {
+                Ok(resp) => {
+                    let next = resp
+                        .links
+                        .as_ref()
+                        .and_then(|links| links.next.as_ref())
+                        .map(|_| number + 1);
+                    let records = resp.data.unwrap_or_default().into_iter().map(Ok);
+                    Some((stream::iter(records).left_stream(), next))
+                }
+                Err(err) => Some((stream::once(future::ready(Err(err))).right_stream(), None)),
+            }
+        }
+    })
+    .flatten()
+}
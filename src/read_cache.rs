@@ -0,0 +1,110 @@
+//! Client-side caching for entity reads.
+//!
+//! ShotGrid's REST API doesn't support conditional requests (via `ETag` or
+//! `If-Modified-Since`), so every read downloads the record in full.
+//!
+//! For callers polling the same records over and over, a [`ReadCache`] can be
+//! handed to [`Session::read_cached()`](`crate::Session::read_cached()`),
+//! which checks the `updated_at` of the record first and only reads the rest
+//! when it has changed since the last time.
+use serde_json::Value;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+#[derive(Debug, Hash, PartialEq, Eq)]
+struct CacheKey {
+    entity: String,
+    id: i32,
+    fields: Option<String>,
+}
+
+#[derive(Debug)]
+struct CachedRead {
+    updated_at: String,
+    body: Value,
+}
+
+/// Remembers the responses of
+/// [`Session::read_cached()`](`crate::Session::read_cached()`), along with the
+/// `updated_at` of each record at the time it was read.
+///
+/// Responses are kept per entity type, id, *and* set of fields, so reading
+/// the same record with different fields will not share a cache entry.
+///
+/// Nothing is ever evicted, so for long running programs it's up to the
+/// caller to [`ReadCache::clear()`] or [`ReadCache::invalidate()`] as needed.
+///
+/// ShotGrid only records `updated_at` to the second. An update made in the
+/// same second as a cached read leaves the stamp as it was, so the stale
+/// response keeps being served until the record is updated again. When that
+/// matters, [`ReadCache::invalidate()`] the record after writing to it.
+#[derive(Debug, Default)]
+pub struct ReadCache {
+    entries: Mutex<HashMap<CacheKey, CachedRead>>,
+}
+
+impl ReadCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget all cached responses.
+    pub async fn clear(&self) {
+        self.entries.lock().await.clear();
+    }
+
+    /// Forget the cached responses for a single record.
+    pub async fn invalidate(&self, entity: &str, id: i32) {
+        self.entries
+            .lock()
+            .await
+            .retain(|key, _| !(key.entity == entity && key.id == id));
+    }
+
+    /// The number of cached responses.
+    pub async fn len(&self) -> usize {
+        self.entries.lock().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.entries.lock().await.is_empty()
+    }
+
+    /// Look up a response, so long as it was cached for the same `updated_at`.
+    pub(crate) async fn get(
+        &self,
+        entity: &str,
+        id: i32,
+        fields: Option<&str>,
+        updated_at: &str,
+    ) -> Option<Value> {
+        self.entries
+            .lock()
+            .await
+            .get(&cache_key(entity, id, fields))
+            .filter(|cached| cached.updated_at == updated_at)
+            .map(|cached| cached.body.clone())
+    }
+
+    pub(crate) async fn insert(
+        &self,
+        entity: &str,
+        id: i32,
+        fields: Option<&str>,
+        updated_at: String,
+        body: Value,
+    ) {
+        self.entries.lock().await.insert(
+            cache_key(entity, id, fields),
+            CachedRead { updated_at, body },
+        );
+    }
+}
+
+fn cache_key(entity: &str, id: i32, fields: Option<&str>) -> CacheKey {
+    CacheKey {
+        entity: entity.to_string(),
+        id,
+        fields: fields.map(String::from),
+    }
+}
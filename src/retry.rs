@@ -0,0 +1,141 @@
+//! Automatic retries for read-only requests.
+//!
+//! Retries are opt-in via [`Client::with_retry()`](`crate::Client::with_retry()`)
+//! and only ever apply to requests which are safe to repeat, such as reading
+//! records or schema. Creates, updates, deletes, and uploads are never retried
+//! since repeating them could result in duplicate writes.
+use crate::{handle_response, Client, Error, Result};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Controls how read-only requests are retried when they fail with a
+/// [retryable](`crate::Error::is_retryable()`) error.
+///
+/// The delay before each retry doubles, starting at `base_delay` and capped at
+/// `max_delay`. A random amount of jitter is applied to each delay so many
+/// clients failing at once don't all retry in lockstep.
+#[derive(Clone, Debug)]
+pub struct RetryConfig {
+    /// How many times to retry a request after the initial attempt.
+    pub max_retries: u32,
+    /// The delay before the first retry.
+    pub base_delay: Duration,
+    /// The longest to wait between any two attempts.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// The upper bound of the delay before retry number `attempt` (counting
+    /// from zero).
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .checked_mul(2_u32.saturating_pow(attempt))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+/// Pick a delay somewhere between half and all of `max`.
+///
+/// This doesn't need to be a good random number, just different enough
+/// between clients, so the clock will do.
+fn jitter(max: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    let half = max / 2;
+    half + half.mul_f64(f64::from(nanos % 1000) / 1000.0)
+}
+
+/// Statuses indicating the server may be able to handle the request if it's
+/// sent again later.
+pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Send a read-only request, retrying per the client's config when it has
+/// one.
+pub(crate) async fn send_idempotent<D>(client: &Client, req: RequestBuilder) -> Result<D>
+where
+    D: DeserializeOwned,
+{
+    let config = match client.retry.as_ref() {
+        Some(config) => config,
+        None => return handle_response(client.send(req).await?).await,
+    };
+
+    let mut attempt = 0;
+    loop {
+        // Read-only requests have no streaming body, so they can always be
+        // cloned.
+        let this_req = req
+            .try_clone()
+            .ok_or_else(|| Error::Unexpected(String::from("Request can't be retried.")))?;
+        let can_retry = attempt < config.max_retries;
+
+        let cause = match client.send(this_req).await {
+            Ok(resp) if can_retry && is_retryable_status(resp.status()) => {
+                format!("Server responded with `{}`", resp.status())
+            }
+            Ok(resp) => return handle_response(resp).await,
+            Err(e) => {
+                let e = Error::from(e);
+                if !(can_retry && e.is_retryable()) {
+                    return Err(e);
+                }
+                e.to_string()
+            }
+        };
+
+        let delay = jitter(config.backoff(attempt));
+        attempt += 1;
+        log::debug!(
+            "Retrying request in {:?} (attempt {} of {}). Cause: `{}`.",
+            delay,
+            attempt,
+            config.max_retries,
+            cause
+        );
+        tokio::time::delay_for(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let config = RetryConfig {
+            max_retries: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+        };
+        assert_eq!(config.backoff(0), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(400));
+        assert_eq!(config.backoff(4), Duration::from_secs(1));
+        assert_eq!(config.backoff(40), Duration::from_secs(1));
+
+        let delay = jitter(config.backoff(2));
+        assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400));
+    }
+}
@@ -0,0 +1,132 @@
+//! The fields to include in a response.
+//!
+//! ShotGrid is inconsistent about how it's told which fields to return. Reads
+//! and searches take a `fields` query parameter, creates and updates take
+//! `options[fields]`, and requests in a batch take an `options` object with a
+//! `fields` key. [`ReturnFields`] knows which is which so the methods on
+//! [`Session`](`crate::Session`) (and anyone building their own requests with
+//! the [`transport`](`crate::transport`)) don't have to remember.
+use serde_json::{json, Value};
+use std::fmt;
+
+/// Where a [`ReturnFields`] is being sent, which decides how it's spelled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReturnFieldsTarget {
+    /// Reading a single record, or searching, via the `fields` query parameter.
+    Read,
+    /// Creating or updating a record, via the `options[fields]` query
+    /// parameter.
+    Write,
+}
+
+impl ReturnFieldsTarget {
+    /// The name of the query parameter for this kind of request.
+    pub fn query_key(self) -> &'static str {
+        match self {
+            ReturnFieldsTarget::Read => "fields",
+            ReturnFieldsTarget::Write => "options[fields]",
+        }
+    }
+}
+
+/// A list of field names to return in a response.
+///
+/// Names are kept in the order given, with surrounding whitespace and
+/// duplicates removed.
+///
+/// ```
+/// use shotgrid_rs::{ReturnFields, ReturnFieldsTarget};
+///
+/// let fields = ReturnFields::parse("code, sg_status_list,code");
+/// assert_eq!(fields.to_string(), "code,sg_status_list");
+/// assert_eq!(
+///     fields.query(ReturnFieldsTarget::Write),
+///     ("options[fields]", String::from("code,sg_status_list"))
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReturnFields {
+    names: Vec<String>,
+}
+
+impl ReturnFields {
+    /// Collect field names, one per item.
+    pub fn new<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut fields = Self::default();
+        for name in names {
+            let name = name.as_ref().trim();
+            if !name.is_empty() && !fields.names.iter().any(|x| x == name) {
+                fields.names.push(name.to_string());
+            }
+        }
+        fields
+    }
+
+    /// Split a comma separated list of field names, such as the `fields`
+    /// given to [`Session::read()`](`crate::Session::read()`).
+    pub fn parse(fields: &str) -> Self {
+        Self::new(fields.split(','))
+    }
+
+    /// The field names, in order.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// The query parameter (name and value) to add to a request of the given
+    /// kind.
+    pub fn query(&self, target: ReturnFieldsTarget) -> (&'static str, String) {
+        (target.query_key(), self.to_string())
+    }
+
+    /// The `options` object for a request in a batch.
+    pub fn batch_options(&self) -> Value {
+        json!({ "fields": self.to_string() })
+    }
+}
+
+impl fmt::Display for ReturnFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.names.join(","))
+    }
+}
+
+impl From<&str> for ReturnFields {
+    fn from(fields: &str) -> Self {
+        Self::parse(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_keys() {
+        let fields = ReturnFields::new(vec!["id", "code"]);
+        assert_eq!(
+            fields.query(ReturnFieldsTarget::Read),
+            ("fields", String::from("id,code"))
+        );
+        assert_eq!(
+            fields.query(ReturnFieldsTarget::Write),
+            ("options[fields]", String::from("id,code"))
+        );
+        assert_eq!(fields.batch_options(), json!({"fields": "id,code"}));
+    }
+
+    #[test]
+    fn test_parse_drops_blanks_and_duplicates() {
+        let fields = ReturnFields::parse(" id,,code , id,project.Project.name");
+        assert_eq!(fields.names(), &["id", "code", "project.Project.name"]);
+        assert!(ReturnFields::parse(" , ").is_empty());
+    }
+}
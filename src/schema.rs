@@ -5,9 +5,14 @@ use std::collections::HashMap;
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SchemaFieldProperties {
     pub default_value: Option<SchemaResponseValue>,
+    /// For `list` and `status_list` fields, a map of each valid value to the
+    /// name shown for it.
+    pub display_values: Option<SchemaResponseValue>,
     pub regex_validation: Option<SchemaResponseValue>,
     pub regex_validation_enabled: Option<SchemaResponseValue>,
     pub summary_default: Option<SchemaResponseValue>,
+    /// For `list` and `status_list` fields, the values the field can hold.
+    pub valid_values: Option<SchemaResponseValue>,
 }
 
 /// <https://developer.shotgridsoftware.com/rest-api/?shell#tocSschemafieldrecord>
@@ -26,6 +31,35 @@ pub struct SchemaFieldRecord {
     pub visible: Option<SchemaResponseValue>,
 }
 
+impl SchemaFieldRecord {
+    /// The name of the field shown in the ShotGrid UI.
+    pub fn display_name(&self) -> Option<&str> {
+        self.name.as_ref()?.as_str()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_ref()?.as_str()
+    }
+
+    /// The kind of data the field holds.
+    ///
+    /// Gives `None` when the data type is missing from the record, or isn't
+    /// one this crate knows about.
+    pub fn data_type(&self) -> Option<FieldDataType> {
+        let value = self.data_type.as_ref()?.value.clone()?;
+        serde_json::from_value(value).ok()
+    }
+
+    /// Whether the field's value can be changed, treating a missing
+    /// `editable` as `false`.
+    pub fn is_editable(&self) -> bool {
+        self.editable
+            .as_ref()
+            .and_then(SchemaResponseValue::as_bool)
+            .unwrap_or(false)
+    }
+}
+
 /// <https://developer.shotgridsoftware.com/rest-api/?shell#tocSschemafieldresponse>
 pub type SchemaFieldResponse = SingleResourceResponse<SchemaFieldRecord, SelfLink>;
 
@@ -54,6 +88,18 @@ pub struct SchemaResponseValue {
     pub editable: Option<bool>,
 }
 
+impl SchemaResponseValue {
+    /// The value, when it's a string.
+    pub fn as_str(&self) -> Option<&str> {
+        self.value.as_ref()?.as_str()
+    }
+
+    /// The value, when it's a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        self.value.as_ref()?.as_bool()
+    }
+}
+
 /// How to perform the grouping for a given summary request.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum FieldDataType {
@@ -95,6 +141,99 @@ pub enum FieldDataType {
     UUID,
     #[serde(rename = "calculated")]
     Calculated,
+    #[serde(rename = "addressing")]
+    Addressing,
+    #[serde(rename = "color")]
+    Color,
+    #[serde(rename = "entity_type")]
+    EntityType,
+    #[serde(rename = "image")]
+    Image,
+    #[serde(rename = "password")]
+    Password,
+    #[serde(rename = "pivot_column")]
+    PivotColumn,
+    #[serde(rename = "serializable")]
+    Serializable,
+    #[serde(rename = "system_task_type")]
+    SystemTaskType,
+    #[serde(rename = "tag_list")]
+    TagList,
+}
+
+/// The commonly needed parts of a `SchemaFieldRecord`, as returned by
+/// [`Session::schema_fields_read_typed()`](`crate::Session::schema_fields_read_typed()`).
+#[derive(Clone, Debug)]
+pub struct FieldInfo {
+    /// The display name of the field.
+    pub name: String,
+    /// The kind of data the field holds, or `None` when the schema doesn't
+    /// say, or it's a data type this crate doesn't know about.
+    pub data_type: Option<FieldDataType>,
+    /// The data type as given in the schema, such as `"status_list"`, which
+    /// is kept even when it isn't one of the known [`FieldDataType`]s.
+    pub data_type_name: Option<String>,
+    pub editable: bool,
+}
+
+impl From<&SchemaFieldRecord> for FieldInfo {
+    fn from(record: &SchemaFieldRecord) -> Self {
+        FieldInfo {
+            name: record.display_name().map(String::from).unwrap_or_default(),
+            data_type: record.data_type(),
+            data_type_name: record
+                .data_type
+                .as_ref()
+                .and_then(SchemaResponseValue::as_str)
+                .map(String::from),
+            editable: record.is_editable(),
+        }
+    }
+}
+
+/// One of the values a `status_list` (or `list`) field can hold, as returned
+/// by [`Session::status_values()`](`crate::Session::status_values()`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusValue {
+    /// The short code stored in the field, such as `ip`.
+    pub code: String,
+    /// The name shown for the value, such as `In Progress`.
+    pub name: Option<String>,
+    /// The background color of the status as `r,g,b`, such as `0,156,255`.
+    ///
+    /// Only `status_list` fields have colors, from their `Status` records.
+    pub color: Option<String>,
+}
+
+impl StatusValue {
+    /// Collect the valid values from a field's schema, along with their
+    /// display names, in the order the schema lists them.
+    pub(crate) fn from_schema(record: &SchemaFieldRecord) -> Vec<Self> {
+        fn value(v: Option<&SchemaResponseValue>) -> Option<&Value> {
+            v.and_then(|v| v.value.as_ref())
+        }
+
+        let properties = record.properties.as_ref();
+        let display_values = value(properties.and_then(|p| p.display_values.as_ref()));
+
+        value(properties.and_then(|p| p.valid_values.as_ref()))
+            .and_then(Value::as_array)
+            .map(|codes| {
+                codes
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|code| StatusValue {
+                        code: code.to_string(),
+                        name: display_values
+                            .and_then(|names| names.get(code))
+                            .and_then(Value::as_str)
+                            .map(String::from),
+                        color: None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 /// <https://developer.shotgridsoftware.com/rest-api/#tocScreatefieldrequest>
@@ -144,3 +283,74 @@ pub struct UpdateFieldRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub project_id: Option<i32>,
 }
+
+/// The body for [`Session::schema_entity_update()`](`crate::Session::schema_entity_update()`).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct UpdateEntityRequest {
+    pub properties: Vec<CreateUpdateFieldProperty>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_field_info_from_record() {
+        let record: SchemaFieldRecord = serde_json::from_value(json!({
+            "data_type": { "value": "status_list", "editable": false },
+            "editable": { "value": true, "editable": false },
+            "name": { "value": "Status", "editable": true },
+            "properties": {}
+        }))
+        .unwrap();
+
+        let info = FieldInfo::from(&record);
+        assert_eq!(info.name, "Status");
+        assert!(matches!(info.data_type, Some(FieldDataType::StatusList)));
+        assert_eq!(info.data_type_name.as_deref(), Some("status_list"));
+        assert!(info.editable);
+    }
+
+    #[test]
+    fn test_field_record_accessors() {
+        let record: SchemaFieldRecord = serde_json::from_value(json!({
+            "data_type": { "value": "entity", "editable": false },
+            "description": { "value": null, "editable": true },
+            "name": { "value": "Sequence", "editable": true },
+            "properties": {}
+        }))
+        .unwrap();
+
+        assert_eq!(record.display_name(), Some("Sequence"));
+        assert_eq!(record.description(), None);
+        assert!(matches!(record.data_type(), Some(FieldDataType::Entity)));
+        assert!(!record.is_editable());
+
+        let record: SchemaFieldRecord = serde_json::from_value(json!({
+            "data_type": { "value": "some_new_type", "editable": false }
+        }))
+        .unwrap();
+        assert!(record.data_type().is_none());
+    }
+
+    #[test]
+    fn test_field_info_from_record_unknown_data_type() {
+        let record: SchemaFieldRecord = serde_json::from_value(json!({
+            "data_type": { "value": "some_new_type", "editable": false },
+            "name": { "value": "Shiny", "editable": true }
+        }))
+        .unwrap();
+        let info = FieldInfo::from(&record);
+        assert!(info.data_type.is_none());
+        assert_eq!(info.data_type_name.as_deref(), Some("some_new_type"));
+
+        let record: SchemaFieldRecord = serde_json::from_value(json!({
+            "name": { "value": "Status", "editable": true }
+        }))
+        .unwrap();
+        let info = FieldInfo::from(&record);
+        assert!(info.data_type.is_none());
+        assert!(info.data_type_name.is_none());
+    }
+}
@@ -1,10 +1,17 @@
 use crate::filters::FinalizedFilters;
-use crate::types::{OptionsParameter, PaginationParameter, ReturnOnly};
-use crate::Session;
+use crate::paginate::paginate;
+use crate::types::{
+    OptionsParameter, PaginationLinks, PaginationParameter, ResourceArrayResponse, ReturnOnly,
+    MAX_PAGE_SIZE,
+};
+use crate::{deserialize_value, Error, ReturnFields, ReturnFieldsTarget, Session};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
 use serde::de::DeserializeOwned;
-use serde_json::json;
+use serde_json::{json, Value};
 use std::borrow::Cow;
+use std::time::Duration;
 
+#[derive(Clone)]
 pub struct SearchBuilder<'a> {
     session: &'a Session<'a>,
     entity: &'a str,
@@ -13,6 +20,24 @@ pub struct SearchBuilder<'a> {
     sort: Option<String>,
     pagination: Option<PaginationParameter>,
     options: Option<OptionsParameter>,
+    debug_filters: bool,
+    expect_type: Option<&'a str>,
+    validate_fields: bool,
+    with_total: bool,
+    timeout: Option<Duration>,
+}
+
+/// A search response along with the paging details ShotGrid sent with it.
+///
+/// Given by [`SearchBuilder::execute_with_meta()`].
+#[derive(Clone, Debug)]
+pub struct SearchResults<D> {
+    /// The response, as [`SearchBuilder::execute()`] would give it.
+    pub response: D,
+    /// The total number of records matching the filters, across every page.
+    ///
+    /// Only present when requested with [`SearchBuilder::with_total()`].
+    pub total: Option<usize>,
 }
 
 impl<'a> SearchBuilder<'a> {
@@ -30,6 +55,11 @@ impl<'a> SearchBuilder<'a> {
             sort: None,
             pagination: None,
             options: None,
+            debug_filters: false,
+            expect_type: None,
+            validate_fields: false,
+            with_total: false,
+            timeout: None,
         }
     }
 
@@ -60,6 +90,10 @@ impl<'a> SearchBuilder<'a> {
         self
     }
 
+    /// Search only active records, or only retired (deleted, but revivable)
+    /// records.
+    ///
+    /// ShotGrid returns only active records when this is not set.
     pub fn return_only(mut self, value: Option<ReturnOnly>) -> Self {
         let mut options = self.options.take().unwrap_or_default();
         if options.include_archived_projects.is_none() && value.is_none() {
@@ -82,11 +116,107 @@ impl<'a> SearchBuilder<'a> {
         self
     }
 
+    /// When enabled, server errors from the search will be returned as
+    /// [`Error::FiltersRejected`](`crate::Error::FiltersRejected`), carrying
+    /// the filter JSON exactly as it was sent.
+    ///
+    /// ShotGrid reports problems with filters as regular server errors so
+    /// *any* server error will include the filters when this is on.
+    ///
+    /// This is off by default since filters may contain sensitive values which
+    /// could end up in logs.
+    pub fn debug_filters(mut self, value: bool) -> Self {
+        self.debug_filters = value;
+        self
+    }
+
+    /// Check that every record in the response has the given `type`, giving
+    /// an [`Error::Unexpected`](`crate::Error::Unexpected`) if any don't.
+    ///
+    /// This guards against deserializing records into a struct meant for
+    /// some other entity type, for example when the entity name passed to
+    /// [`Session::search()`](`crate::Session::search()`) has a typo.
+    pub fn expect_type(mut self, value: &'a str) -> Self {
+        self.expect_type = Some(value);
+        self
+    }
+
+    /// When enabled, check the requested fields exist on the entity before
+    /// running the search, giving an [`Error::Unexpected`](`crate::Error::Unexpected`)
+    /// listing any that don't.
+    ///
+    /// ShotGrid silently leaves unknown fields out of the response, so a typo
+    /// in a field name otherwise goes unnoticed.
+    ///
+    /// For deep fields, such as `sg_sequence.Sequence.code`, the link field
+    /// must exist on the entity and the last field must exist on the linked
+    /// entity type.
+    ///
+    /// The entity's field names are read from the schema on first use, then
+    /// remembered by the session. See
+    /// [`Session::clear_field_names()`](`crate::Session::clear_field_names()`).
+    pub fn validate_fields(mut self, value: bool) -> Self {
+        self.validate_fields = value;
+        self
+    }
+
+    /// When enabled, ask ShotGrid for the total number of matching records
+    /// (across every page) along with the current page. Use
+    /// [`execute_with_meta()`](`SearchBuilder::execute_with_meta()`) to get
+    /// at it.
+    ///
+    /// This saves running a second query to count the records when both the
+    /// first page and the total are needed.
+    pub fn with_total(mut self, value: bool) -> Self {
+        self.with_total = value;
+        self
+    }
+
+    /// Give the search `value` to complete, rather than the HTTP client's
+    /// timeout. See [`Client`](`crate::Client`#timeouts).
+    pub fn timeout(mut self, value: Duration) -> Self {
+        self.timeout = Some(value);
+        self
+    }
+
     pub async fn execute<D: 'static>(self) -> crate::Result<D>
     where
         D: DeserializeOwned,
     {
-        let mut query: Vec<(&str, Cow<str>)> = vec![("fields", Cow::Borrowed(self.fields))];
+        deserialize_value(&self.execute_value().await?)
+    }
+
+    /// Run the search, also giving the total number of matching records when
+    /// [`with_total()`](`SearchBuilder::with_total()`) is enabled.
+    pub async fn execute_with_meta<D>(self) -> crate::Result<SearchResults<D>>
+    where
+        D: DeserializeOwned,
+    {
+        let resp = self.execute_value().await?;
+        let total = resp["meta"]["paging_info"]["entity_count"]
+            .as_u64()
+            .map(|count| count as usize);
+        Ok(SearchResults {
+            response: deserialize_value(&resp)?,
+            total,
+        })
+    }
+
+    async fn execute_value(self) -> crate::Result<Value> {
+        if let Some(pag) = self.pagination.as_ref() {
+            pag.validate()?;
+        }
+
+        if self.validate_fields {
+            self.session
+                .check_fields_exist(self.entity, self.fields)
+                .await?;
+        }
+
+        let mut query: Vec<(&str, Cow<str>)> = {
+            let (key, fields) = ReturnFields::parse(self.fields).query(ReturnFieldsTarget::Read);
+            vec![(key, Cow::Owned(fields))]
+        };
         if let Some(pag) = self.pagination {
             if let Some(number) = pag.number {
                 query.push(("page[number]", Cow::Owned(format!("{}", number))));
@@ -124,23 +254,680 @@ impl<'a> SearchBuilder<'a> {
                 ));
             }
         }
+        if self.with_total {
+            query.push(("options[return_paging_info]", Cow::Borrowed("true")));
+        }
+        let body = json!({"filters": self.filters}).to_string();
         let (sg, token) = self.session.get_sg().await?;
-        let req = sg
+        let mut req = sg
             .http
             .post(&format!(
-                "{}/api/v1/entity/{}/_search",
-                sg.sg_server, self.entity
+                "{}{}/entity/{}/_search",
+                sg.sg_server, sg.api_base, self.entity
             ))
             .query(&query)
             .header("Accept", "application/json")
-            .bearer_auth(&token)
-            .header("Content-Type", self.filters.get_mime())
-            // The content type is being set to ShotGrid's custom mime types
-            // to indicate the shape of the filter payload. Do not be tempted to
-            // use `.json()` here instead of `.body()` or you'll end up
-            // reverting the header set above.
-            .body(json!({"filters": self.filters}).to_string());
-
-        crate::handle_response(req.send().await?).await
+            .bearer_auth(&token);
+        if let Some(timeout) = self.timeout {
+            req = req.timeout(timeout);
+        }
+
+        let resp: Value = match sg
+            .send_with_filter_mime(req, self.filters.get_mime(), body.clone())
+            .await
+        {
+            Err(Error::ServerError(errors)) if self.debug_filters => {
+                return Err(Error::FiltersRejected {
+                    errors,
+                    filters: body,
+                })
+            }
+            other => other?,
+        };
+
+        if let Some(expected) = self.expect_type {
+            check_record_types(&resp, expected)?;
+        }
+
+        Ok(resp)
+    }
+
+    /// Get the matching records one at a time, following the pagination links
+    /// until there are no more pages.
+    ///
+    /// Paging starts from the page set by [`number()`](`SearchBuilder::number()`)
+    /// (or the first page), with each page holding as many records as set by
+    /// [`size()`](`SearchBuilder::size()`).
+    pub fn stream<R>(self) -> impl Stream<Item = crate::Result<R>> + 'a
+    where
+        R: DeserializeOwned + 'static,
+    {
+        let first_page = self
+            .pagination
+            .as_ref()
+            .and_then(|pagination| pagination.number)
+            .unwrap_or(1);
+        paginate(first_page, move |page| {
+            self.clone()
+                .number(Some(page))
+                .execute::<ResourceArrayResponse<R, PaginationLinks>>()
+        })
+    }
+
+    /// Get all the matching records, fetching up to `concurrency` pages at a
+    /// time.
+    ///
+    /// The first page is read along with the total number of matching
+    /// records, then the rest of the pages are requested at once rather
+    /// than one after another as with [`stream()`](`SearchBuilder::stream()`).
+    /// Records are given in the same order as the pages.
+    ///
+    /// Paging starts from the page set by [`number()`](`SearchBuilder::number()`)
+    /// (or the first page), with each page holding as many records as set by
+    /// [`size()`](`SearchBuilder::size()`), or [`MAX_PAGE_SIZE`] when not
+    /// set. Since pages are read independently, records created or deleted
+    /// part way through may shift between pages, causing records to be
+    /// skipped or repeated; sort on a stable field such as `id` and avoid
+    /// exporting data that's being modified.
+    pub async fn collect_all<R>(self, concurrency: usize) -> crate::Result<Vec<R>>
+    where
+        R: DeserializeOwned + 'static,
+    {
+        let pagination = self.pagination.clone().unwrap_or_default();
+        let first_page = pagination.number.unwrap_or(1);
+        let page_size = pagination.size.unwrap_or(MAX_PAGE_SIZE);
+        let search = self.size(Some(page_size));
+
+        let SearchResults { response, total } = search
+            .clone()
+            .with_total(true)
+            .execute_with_meta::<ResourceArrayResponse<R, PaginationLinks>>()
+            .await?;
+        let mut records = response.data.unwrap_or_default();
+
+        let total = match total {
+            Some(total) => total,
+            None => {
+                // Without the total, fall back to reading pages one at a time.
+                if records.len() == page_size {
+                    let rest: Vec<R> = search
+                        .number(Some(first_page + 1))
+                        .stream()
+                        .try_collect()
+                        .await?;
+                    records.extend(rest);
+                }
+                return Ok(records);
+            }
+        };
+        let last_page = total / page_size + usize::from(total % page_size != 0);
+
+        let search = search.with_total(false);
+        let pages: Vec<Vec<R>> = stream::iter(first_page + 1..=last_page)
+            .map(|page| {
+                search
+                    .clone()
+                    .number(Some(page))
+                    .execute::<ResourceArrayResponse<R, PaginationLinks>>()
+            })
+            .buffered(concurrency.max(1))
+            .map_ok(|resp| resp.data.unwrap_or_default())
+            .try_collect()
+            .await?;
+        records.extend(pages.into_iter().flatten());
+        Ok(records)
+    }
+}
+
+/// Verify the `type` of each record in a search response is `expected`.
+fn check_record_types(resp: &Value, expected: &str) -> crate::Result<()> {
+    let records = resp["data"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for record in records {
+        match record["type"].as_str() {
+            Some(actual) if actual == expected => {}
+            actual => {
+                return Err(Error::Unexpected(format!(
+                    "Expected records of type `{}`, got `{}` (id `{}`).",
+                    expected,
+                    actual.unwrap_or("null"),
+                    record["id"]
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_record_types() {
+        let resp = json!({
+            "data": [
+                { "id": 1, "type": "Shot" },
+                { "id": 2, "type": "Asset" }
+            ]
+        });
+        assert!(check_record_types(&resp, "Shot").is_err());
+        assert!(check_record_types(&json!({ "data": [resp["data"][0]] }), "Shot").is_ok());
+        assert!(check_record_types(&json!({ "data": [] }), "Shot").is_ok());
+    }
+}
+
+#[cfg(test)]
+mod mock_tests {
+    use super::*;
+    use crate::filters::{self, field};
+    use crate::Client;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mock_rejected_search(mock_server: &MockServer) {
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+        let error_body = r##"
+        {
+          "errors": [
+            {
+              "id": "xxxxx",
+              "status": 400,
+              "code": 103,
+              "title": "Invalid filters",
+              "detail": "Invalid filter field: sg_nonexistent",
+              "source": null,
+              "meta": null
+            }
+          ]
+        }
+        "##;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot/_search"))
+            .respond_with(ResponseTemplate::new(400).set_body_raw(error_body, "application/json"))
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_timeout_overrides_client() {
+        let mock_server = MockServer::start().await;
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot/_search"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(r#"{"data": []}"#, "application/json")
+                    .set_delay(std::time::Duration::from_millis(500)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let http = crate::transport::reqwest::Client::builder()
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let sg = Client::with_transport(mock_server.uri(), None, None, http);
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let filters = filters::empty();
+        let err = session
+            .search("Shot", "id", &filters)
+            .execute::<Value>()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Timeout(_)), "{:?}", err);
+
+        session
+            .search("Shot", "id", &filters)
+            .timeout(Duration::from_secs(5))
+            .execute::<Value>()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wrong_shape_keeps_body() {
+        let mock_server = MockServer::start().await;
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"data": [{"id": "sh010", "type": "Shot"}]}"#,
+                "application/json",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let filters = filters::empty();
+        let search = session.search("Shot", "id", &filters);
+        match search
+            .clone()
+            .execute::<crate::types::PaginatedRecordResponse>()
+            .await
+        {
+            Err(Error::DeserializationFailed { body, .. }) => assert!(body.contains("sh010")),
+            other => panic!("Expected Error::DeserializationFailed, got {:?}", other),
+        }
+        match search
+            .execute_with_meta::<crate::types::PaginatedRecordResponse>()
+            .await
+        {
+            Err(Error::DeserializationFailed { body, .. }) => assert!(body.contains("sh010")),
+            other => panic!("Expected Error::DeserializationFailed, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_total() {
+        let mock_server = MockServer::start().await;
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot/_search"))
+            .and(query_param("options[return_paging_info]", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"data": [], "meta": {"paging_info": {"entity_count": 1234}}}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let filters = filters::empty();
+        let results = session
+            .search("Shot", "id", &filters)
+            .size(Some(1))
+            .with_total(true)
+            .execute_with_meta::<ResourceArrayResponse<Value, PaginationLinks>>()
+            .await
+            .unwrap();
+        assert_eq!(results.total, Some(1234));
+        assert!(results.response.data.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_collect_all() {
+        let mock_server = MockServer::start().await;
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot/_search"))
+            .and(query_param("options[return_paging_info]", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"data": [{"id": 1}, {"id": 2}], "meta": {"paging_info": {"entity_count": 5}}}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        // Page 2 responds after page 3, so out-of-order completion would show.
+        for (page, body, delay) in &[
+            ("2", r#"{"data": [{"id": 3}, {"id": 4}]}"#, 200),
+            ("3", r#"{"data": [{"id": 5}]}"#, 0),
+        ] {
+            Mock::given(method("POST"))
+                .and(path("/api/v1/entity/Shot/_search"))
+                .and(query_param("page[number]", *page))
+                .and(query_param("page[size]", "2"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_raw(*body, "application/json")
+                        .set_delay(Duration::from_millis(*delay)),
+                )
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let filters = filters::empty();
+        let records: Vec<Value> = session
+            .search("Shot", "id", &filters)
+            .size(Some(2))
+            .collect_all(2)
+            .await
+            .unwrap();
+        let ids: Vec<_> = records.iter().map(|r| r["id"].as_i64().unwrap()).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_pagination() {
+        // No mocks, since nothing should be sent.
+        let mock_server = MockServer::start().await;
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg.session_from_tokens(
+            crate::TokenResponse {
+                token_type: String::from("Bearer"),
+                access_token: String::from("$$ACCESS_TOKEN$$"),
+                expires_in: 600,
+                refresh_token: String::from("$$REFRESH_TOKEN$$"),
+            },
+            None,
+            None,
+        );
+
+        let filters = filters::empty();
+        for search in vec![
+            session.search("Shot", "id", &filters).number(Some(0)),
+            session.search("Shot", "id", &filters).size(Some(0)),
+            session.search("Shot", "id", &filters).size(Some(501)),
+        ] {
+            let err = search.execute::<Value>().await.unwrap_err();
+            assert!(matches!(err, Error::InvalidPagination(_)), "{:?}", err);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_debug_filters_includes_payload() {
+        let mock_server = MockServer::start().await;
+        mock_rejected_search(&mock_server).await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let filters = filters::basic(&[field("sg_nonexistent").is("abc")]);
+        let err = session
+            .search("Shot", "id", &filters)
+            .debug_filters(true)
+            .execute::<Value>()
+            .await
+            .unwrap_err();
+
+        let expected = r#"{"filters":[["sg_nonexistent","is","abc"]]}"#;
+        match &err {
+            Error::FiltersRejected { errors, filters } => {
+                assert_eq!(errors[0].code, Some(103));
+                assert_eq!(filters, expected);
+            }
+            other => panic!("Unexpected error: {:?}", other),
+        }
+        assert!(err.to_string().contains(expected));
+    }
+
+    #[tokio::test]
+    async fn test_debug_filters_off_by_default() {
+        let mock_server = MockServer::start().await;
+        mock_rejected_search(&mock_server).await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let filters = filters::basic(&[field("sg_nonexistent").is("abc")]);
+        let err = session
+            .search("Shot", "id", &filters)
+            .execute::<Value>()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, Error::ServerError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_return_only_retired() {
+        let mock_server = MockServer::start().await;
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot/_search"))
+            .and(query_param("options[return_only]", "retired"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"data": [{"id": 1, "type": "Shot"}], "links": {"self": "/"}}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let filters = filters::empty();
+        let resp: Value = session
+            .search("Shot", "id", &filters)
+            .return_only(Some(ReturnOnly::Retired))
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(resp["data"][0]["id"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_validate_fields() {
+        let mock_server = MockServer::start().await;
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schema/Shot/fields"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"data": {"id": {}, "code": {}, "sg_sequence": {}}, "links": {"self": "/"}}"#,
+                "application/json",
+            ))
+            // The field names are remembered after the first search.
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schema/Sequence/fields"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"data": {"id": {}, "code": {}}, "links": {"self": "/"}}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"data": [], "links": {"self": "/"}}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let filters = filters::empty();
+        let err = session
+            .search("Shot", "code,naem", &filters)
+            .validate_fields(true)
+            .execute::<Value>()
+            .await
+            .unwrap_err();
+        match err {
+            Error::Unexpected(msg) => assert!(msg.contains("naem") && !msg.contains("code")),
+            other => panic!("Expected Error::Unexpected, got {:?}", other),
+        }
+
+        let err = session
+            .search(
+                "Shot",
+                "sg_sequence.Sequence.naem,sg_seq.Sequence.code",
+                &filters,
+            )
+            .validate_fields(true)
+            .execute::<Value>()
+            .await
+            .unwrap_err();
+        match err {
+            Error::Unexpected(msg) => assert!(
+                msg.contains("sg_sequence.Sequence.naem") && msg.contains("sg_seq.Sequence.code")
+            ),
+            other => panic!("Expected Error::Unexpected, got {:?}", other),
+        }
+
+        session
+            .search("Shot", "id,type,code,sg_sequence.Sequence.code", &filters)
+            .validate_fields(true)
+            .execute::<Value>()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_expect_type_mismatch() {
+        let mock_server = MockServer::start().await;
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"data": [{"id": 1, "type": "Asset"}], "links": {"self": "/"}}"#,
+                "application/json",
+            ))
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let filters = filters::empty();
+        let err = session
+            .search("Shot", "id", &filters)
+            .expect_type("Shot")
+            .execute::<Value>()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Unexpected(_)));
+
+        // Without the check, the records come back as-is.
+        let resp: Value = session
+            .search("Shot", "id", &filters)
+            .execute()
+            .await
+            .unwrap();
+        assert_eq!(resp["data"][0]["type"], "Asset");
     }
 }
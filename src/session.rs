@@ -3,32 +3,91 @@
 //! The high-level aim is to give the caller an API that doesn't ever ask for an
 //! access token. Instead the session will pass the tokens around for the caller,
 //! and refresh it as needed, behind the scenes.
-use crate::filters::FinalizedFilters;
+use crate::filters::{self, field, EntityRef, FinalizedFilters};
 use crate::text_search::TextSearchBuilder;
 use crate::types::{
-    AltImages, BatchedRequestsResponse, CreateFieldRequest, CreateUpdateFieldProperty,
-    EntityActivityStreamResponse, EntityIdentifier, FieldDataType, FieldHashResponse,
-    HierarchyExpandRequest, HierarchyExpandResponse, HierarchySearchRequest,
-    HierarchySearchResponse, ProjectAccessUpdateResponse, SchemaEntityResponse,
-    SchemaFieldResponse, SchemaFieldsResponse, SummaryField, UpdateFieldRequest,
-    UploadInfoResponse,
+    AltImages, BatchedRequestsResponse, CreateFieldRequest, CreateUpdateFieldProperty, Entity,
+    EntityIdentifier, EntityThreadContentsResponse, FieldDataType, FieldHashResponse, FieldInfo,
+    FollowRecord, FollowerRecord, GetWorkDayRulesResponse, HierarchyExpandRequest,
+    HierarchyExpandResponse, HierarchySearchRequest, HierarchySearchResponse, MultiEntityMode,
+    PaginatedRecordResponse, PaginationLinks, ProjectAccessUpdateResponse, Record,
+    ResourceArrayResponse, SchemaEntitiesResponse, SchemaEntityResponse, SchemaFieldRecord,
+    SchemaFieldResponse, SchemaFieldsResponse, SchemaResponseValue, SelfLink, SingleRecordResponse,
+    SingleResourceResponse, SitePreferences, StatusValue, SummaryField, UpdateEntityRequest,
+    UpdateFieldRequest, UpdateWorkDayRulesRequest, UpdateWorkDayRulesResponse, UploadInfoResponse,
+    WorkDayRulesData,
 };
 use crate::{
-    handle_response, summarize, upload, EntityRelationshipReadReqBuilder, Error, Result,
-    SearchBuilder, SummarizeReqBuilder, UploadReqBuilder,
+    handle_response, summarize, upload, CreateBuilder, EntityActivityStreamReadReqBuilder,
+    EntityRelationshipReadReqBuilder, Error, FileDownload, ReadCache, Result, SearchBuilder,
+    SummarizeReqBuilder, UpdateBuilder, UploadReqBuilder, UploadResult,
 };
-use crate::{Client, TokenResponse};
+use crate::{Client, ReturnFields, ReturnFieldsTarget, TokenResponse};
+use futures::future::try_join_all;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use reqwest::RequestBuilder;
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 // Note that since each Session holds refresh tokens *which can only be used once*
 // This struct should *not* implement `Clone`.
 pub struct Session<'sg> {
-    last_refresh: u64,
-    tokens: tokio::sync::Mutex<TokenResponse>,
+    tokens: tokio::sync::Mutex<TokenState>,
     client: &'sg Client,
+    /// Who the session was authenticated as, when known.
+    login: Option<Login>,
+    /// Cached result of `current_user()`.
+    current_user: tokio::sync::Mutex<Option<Entity>>,
+    /// Language to request for localized schema display names.
+    locale: Option<String>,
+    /// How long to give schema requests, overriding the HTTP client.
+    schema_timeout: Option<Duration>,
+    /// Field names per entity type, for `SearchBuilder::validate_fields()`.
+    field_names: tokio::sync::Mutex<HashMap<String, HashSet<String>>>,
+    /// Whether to refresh the tokens before they expire.
+    auto_refresh: bool,
+}
+
+/// The tokens held by a session, along with when they were acquired.
+///
+/// These are kept together behind the same mutex so a refresh updates both at
+/// once.
+struct TokenState {
+    tokens: TokenResponse,
+    /// Unix timestamp (in seconds) of when `tokens` were acquired.
+    last_refresh: u64,
+}
+
+impl TokenState {
+    /// Check to see if we should try to refresh early, given the current unix
+    /// timestamp (in seconds).
+    fn expiring(&self, now: u64) -> bool {
+        now.saturating_sub(self.last_refresh) as i64
+            > self.tokens.expires_in - TOKEN_REFRESH_SLOP as i64
+    }
+
+    /// Replace the tokens with a newly refreshed set, acquired at `now`.
+    fn refreshed(&mut self, tokens: TokenResponse, now: u64) {
+        self.tokens = tokens;
+        self.last_refresh = now;
+    }
+}
+
+/// The user a session was authenticated as.
+///
+/// Persist this alongside the session's tokens so a session resumed from them
+/// still knows who it belongs to.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Login {
+    /// The `login` of a `HumanUser`, either from a credential challenge or from
+    /// an `ApiUser` running "sudo as."
+    HumanUser(String),
+    /// The script name of an `ApiUser`.
+    ApiUser(String),
 }
 
 // To account for time elapsed between the auth request and the
@@ -37,39 +96,126 @@ pub struct Session<'sg> {
 // This value will be subtracted from the TTL to shorten it.
 const TOKEN_REFRESH_SLOP: u64 = 90;
 
+// When a batch is rejected and its requests are retried one at a time, at most
+// this many are in flight at once so a large batch doesn't hit the server all
+// at once.
+const BATCH_RETRY_CONCURRENCY: usize = 8;
+
 impl<'sg> Session<'sg> {
     pub(crate) fn new(sg: &'sg Client, initial_auth: TokenResponse) -> Self {
+        Self::with_last_refresh(sg, initial_auth, unix_now())
+    }
+
+    /// Like `new()` but for tokens that were acquired at some point in the
+    /// past. `last_refresh` is the unix timestamp (in seconds) of when the
+    /// tokens were acquired.
+    pub(crate) fn with_last_refresh(
+        sg: &'sg Client,
+        initial_auth: TokenResponse,
+        last_refresh: u64,
+    ) -> Self {
         log::trace!("New session.");
         Self {
             client: sg,
-            tokens: tokio::sync::Mutex::new(initial_auth),
-            last_refresh: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            tokens: tokio::sync::Mutex::new(TokenState {
+                tokens: initial_auth,
+                last_refresh,
+            }),
+            login: None,
+            current_user: tokio::sync::Mutex::new(None),
+            locale: None,
+            schema_timeout: None,
+            field_names: tokio::sync::Mutex::new(HashMap::new()),
+            auto_refresh: true,
+        }
+    }
+
+    /// Record who the session was authenticated as, enabling `current_user()`.
+    pub(crate) fn with_login(mut self, login: Login) -> Self {
+        self.login = Some(login);
+        self
+    }
+
+    /// The user the session was authenticated as, if known.
+    pub fn login(&self) -> Option<&Login> {
+        self.login.as_ref()
+    }
+
+    /// Whether the session acts as an `ApiUser`, rather than a `HumanUser`
+    /// (directly, or via "sudo as").
+    pub(crate) fn is_api_user(&self) -> bool {
+        matches!(self.login, Some(Login::ApiUser(_)))
+    }
+
+    /// Request schema display names in the given language (for example `"ja"`
+    /// or `"fr-FR"`) rather than the site default.
+    ///
+    /// This sets the `Accept-Language` header on requests made by the
+    /// `schema_*_read()` methods. Languages not supported by the ShotGrid site
+    /// will fall back to the site default.
+    pub fn with_locale(mut self, lang: &str) -> Self {
+        self.locale = Some(lang.to_string());
+        self
+    }
+
+    /// Give the `schema_*()` methods `value` to complete, rather than the HTTP
+    /// client's timeout. See [`Client`](`crate::Client`#timeouts).
+    pub fn with_schema_timeout(mut self, value: Duration) -> Self {
+        self.schema_timeout = Some(value);
+        self
+    }
+
+    /// Set the timeout for a schema request, if one was given.
+    fn schema_timeout(&self, req: RequestBuilder) -> RequestBuilder {
+        match self.schema_timeout {
+            Some(timeout) => req.timeout(timeout),
+            None => req,
         }
     }
 
+    /// Add the `Accept-Language` header to the request, if a locale was set.
+    fn localize(&self, req: RequestBuilder) -> RequestBuilder {
+        match &self.locale {
+            Some(lang) => req.header("Accept-Language", lang),
+            None => req,
+        }
+    }
+
+    /// Turn the automatic refresh of expiring tokens on or off (it's on by
+    /// default).
+    ///
+    /// With auto refresh off, the current access token is used as-is even
+    /// once it has expired, and requests will fail with
+    /// [`Error::Unauthorized`] when the server rejects it. This is mostly
+    /// useful for testing around token expiry, or for callers who want to
+    /// manage refreshes themselves.
+    pub fn set_auto_refresh(&mut self, enabled: bool) {
+        self.auto_refresh = enabled;
+    }
+
     /// Get a client/token pair to use to run queries.
     /// Will attempt to refresh the token if it looks ready to expire.
     ///
     /// This is mostly just a stepping stone to bridge session vs pre-session
     /// code.
     pub(crate) async fn get_sg(&self) -> Result<(&Client, String)> {
-        if self.token_expiring().await {
+        if self.auto_refresh && self.token_expiring().await {
             self.refresh_token().await?;
         }
-        Ok((self.client, self.tokens.lock().await.access_token.clone()))
+        Ok((
+            self.client,
+            self.tokens.lock().await.tokens.access_token.clone(),
+        ))
+    }
+
+    /// The client the session was created by.
+    pub(crate) fn client(&self) -> &'sg Client {
+        self.client
     }
 
     /// Check to see if we should try to refresh early.
     async fn token_expiring(&self) -> bool {
-        let ttl = { self.tokens.lock().await.expires_in };
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        (now - self.last_refresh) as i64 > ttl - TOKEN_REFRESH_SLOP as i64
+        self.tokens.lock().await.expiring(unix_now())
     }
 
     /// `Session` needs to be able to refresh the auth token when:
@@ -78,36 +224,119 @@ impl<'sg> Session<'sg> {
     ///
     /// Refresh tokens can only be *used once* or the refresh request will be
     /// denied.
-    /// In light of this, the tokens field has been wrapped in a mutex to try and
-    /// restrict concurrent access.
-    ///
-    /// This has implications for cloning - we may need to add an Arc that can be
-    /// cloned so that all clones of a Session share the same mutex.
+    /// In light of this, the tokens are wrapped in a mutex so only one task
+    /// can refresh at a time. Tasks that were waiting on the lock while
+    /// another refreshed will see the new tokens and skip their own refresh.
     async fn refresh_token(&self) -> Result<()> {
-        let mut tokens = self.tokens.lock().await;
+        let mut state = self.tokens.lock().await;
+
+        // Check again now that we hold the lock since another task may have
+        // refreshed while we were waiting.
+        let now = unix_now();
+        if !state.expiring(now) {
+            log::trace!("Tokens were refreshed by another task.");
+            return Ok(());
+        }
 
-        *tokens = self
+        let tokens = self
             .client
             .authenticate(&[
                 ("grant_type", "refresh"),
-                ("refresh_token", &tokens.refresh_token),
+                ("refresh_token", &state.tokens.refresh_token),
             ])
             .await?;
+        state.refreshed(tokens, now);
 
         Ok(())
     }
 
     /// Batch execute requests
+    ///
+    /// Each entry in the `requests` array of `data` has a `request_type` of
+    /// `create`, `update`, or `delete`, which are the only operations the
+    /// batch endpoint accepts. Other requests, such as
+    /// [`Session::summarize()`], can't be batched; to cut down on the time
+    /// spent waiting on several of them, run them concurrently instead (for
+    /// example with `futures::future::try_join_all`).
+    ///
+    /// When any one of the requests is rejected, none of them are applied and
+    /// the whole batch fails. See [`Session::batch_each()`] to find out which
+    /// requests were rejected and let the rest go through.
+    /// <https://developer.shotgridsoftware.com/rest-api/#batch-requests>
     pub async fn batch(&self, data: Value) -> Result<BatchedRequestsResponse> {
+        self.send_batch(&data).await
+    }
+
+    /// Post `payload` (an object with a `requests` array) to the batch
+    /// endpoint.
+    async fn send_batch<B, D>(&self, payload: &B) -> Result<D>
+    where
+        B: Serialize + ?Sized,
+        D: DeserializeOwned,
+    {
         let (sg, token) = self.get_sg().await?;
         let req = sg
             .http
-            .post(&format!("{}/api/v1/entity/_batch", sg.sg_server))
+            .post(&format!("{}{}/entity/_batch", sg.sg_server, sg.api_base))
             .bearer_auth(token)
             .header("Accept", "application/json")
-            .json(&data);
+            .json(payload);
+
+        handle_response(sg.send(req).await?).await
+    }
+
+    /// Run a batch of requests, reporting the outcome of each one rather than
+    /// failing them all together.
+    ///
+    /// Each of the `requests` is an entry for the `requests` array of a
+    /// [`Session::batch()`], such as
+    /// `{"request_type": "create", "entity": "Shot", "data": {"code": "sh010"}}`.
+    ///
+    /// The requests are first sent as a single batch. Batch requests are
+    /// transactional, so when ShotGrid rejects any one of them nothing is
+    /// applied. In that case each request is retried as a batch of its own so
+    /// the rest can still go through, and the ones ShotGrid rejects give an
+    /// [`Error::ServerError`] with the errors it reported for that request.
+    ///
+    /// Any other failure of the first batch (a timeout, say) is returned as-is,
+    /// since the requests may or may not have been applied.
+    ///
+    /// Results are returned in the same order as `requests`, each holding the
+    /// record from the response. This gives up the all-or-nothing behavior of
+    /// [`Session::batch()`], so use that when the requests must be applied
+    /// together.
+    /// <https://developer.shotgridsoftware.com/rest-api/#batch-requests>
+    pub async fn batch_each(&self, requests: Vec<Value>) -> Result<Vec<Result<Value>>> {
+        if requests.is_empty() {
+            return Ok(vec![]);
+        }
+
+        match self.batch_values(&requests).await {
+            Ok(records) => Ok(records.into_iter().map(Ok).collect()),
+            Err(Error::ServerError(errors)) => {
+                log::debug!(
+                    "Batch rejected, retrying individually. Cause: `{:?}`.",
+                    errors
+                );
+                let results = stream::iter(requests.iter())
+                    .map(|request| async move {
+                        let records = self.batch_values(std::slice::from_ref(request)).await?;
+                        Ok(records.into_iter().next().unwrap_or_default())
+                    })
+                    .buffered(BATCH_RETRY_CONCURRENCY)
+                    .collect()
+                    .await;
+                Ok(results)
+            }
+            Err(e) => Err(e),
+        }
+    }
 
-        handle_response(req.send().await?).await
+    /// Send the given entries as a batch, giving the `data` from the response.
+    async fn batch_values(&self, requests: &[Value]) -> Result<Vec<Value>> {
+        let resp: ResourceArrayResponse<Value, SelfLink> =
+            self.send_batch(&json!({ "requests": requests })).await?;
+        Ok(resp.data.unwrap_or_default())
     }
 
     /// Create a new entity.
@@ -118,40 +347,262 @@ impl<'sg> Session<'sg> {
     /// `fields` can be specified to limit the returned fields from the request.
     /// `fields` is an optional comma separated list of field names to return in the response.
     /// Passing `None` will use the default behavior of returning _all fields_.
-    pub async fn create<D: 'static>(
+    ///
+    /// `data` can be anything which serializes to such an object, whether a
+    /// [`Value`] or a struct of your own.
+    pub async fn create<B, D: 'static>(
         &self,
         entity: &str,
-        data: Value,
+        data: &B,
         fields: Option<&str>,
     ) -> Result<D>
     where
+        B: Serialize + ?Sized,
         D: DeserializeOwned,
     {
         let (sg, token) = self.get_sg().await?;
         let mut req = sg
             .http
-            .post(&format!("{}/api/v1/entity/{}", sg.sg_server, entity,))
+            .post(&format!(
+                "{}{}/entity/{}",
+                sg.sg_server, sg.api_base, entity,
+            ))
             .bearer_auth(token)
             .header("Accept", "application/json")
-            .json(&data);
+            .json(data);
 
         if let Some(fields) = fields {
-            req = req.query(&[("options[fields]", fields)]);
+            req = req.query(&[ReturnFields::parse(fields).query(ReturnFieldsTarget::Write)]);
+        }
+        handle_response(sg.send(req).await?).await
+    }
+
+    /// Create a new entity, configuring the request with a builder.
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> shotgrid_rs::Result<()> {
+    /// # use shotgrid_rs::Client;
+    /// # use shotgrid_rs::types::SingleRecordResponse;
+    /// # use serde_json::json;
+    /// # let sg = Client::new(String::from("https://shotgrid.example.com"), None, None)?;
+    /// # let session = sg.authenticate_script().await?;
+    /// let shot: SingleRecordResponse = session
+    ///     .create_builder("Shot", &json!({"code": "sh010"}))
+    ///     .return_fields(&["id", "code"])
+    ///     .execute()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_builder<'a, B>(&'a self, entity: &'a str, data: &'a B) -> CreateBuilder<'a, B>
+    where
+        B: Serialize + ?Sized,
+    {
+        CreateBuilder::new(self, entity, data)
+    }
+
+    /// Create several records of the same entity type in a single batch
+    /// request.
+    ///
+    /// Each item in `records` is used as the `data` for a single create, as
+    /// with [`Session::create()`]. The created records are returned in the
+    /// same order as `records`.
+    ///
+    /// `fields` is an optional comma separated list of field names to return
+    /// for each record. Passing `None` will use the default behavior of
+    /// returning _all fields_.
+    ///
+    /// Batch requests are transactional, so if any one of the creates fails
+    /// none of the records will be created.
+    /// <https://developer.shotgridsoftware.com/rest-api/#batch-requests>
+    pub async fn create_many<R>(
+        &self,
+        entity: &str,
+        records: Vec<Value>,
+        fields: Option<&str>,
+    ) -> Result<Vec<R>>
+    where
+        R: DeserializeOwned + 'static,
+    {
+        if records.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let resp: ResourceArrayResponse<R, SelfLink> = self
+            .send_batch(&batch_create_payload(entity, records, fields))
+            .await?;
+        Ok(resp.data.unwrap_or_default())
+    }
+
+    /// Get a copy of the tokens the session is currently using.
+    ///
+    /// This is intended for callers wishing to persist their tokens, along
+    /// with the [`Session::login()`], to later resume with
+    /// [`Client::session_from_tokens()`].
+    ///
+    /// Be aware the refresh token *can only be used once*. If this session
+    /// goes on to refresh its tokens after you've taken a copy, the persisted
+    /// refresh token is spent and must not be reused.
+    pub async fn current_tokens(&self) -> TokenResponse {
+        self.tokens.lock().await.tokens.clone()
+    }
+
+    /// Look up the user this session was authenticated as.
+    ///
+    /// For sessions authenticated with [`Client::authenticate_user()`] or
+    /// [`Client::authenticate_script_as_user()`] this will be a `HumanUser`.
+    /// For [`Client::authenticate_script()`] this will be an `ApiUser`.
+    ///
+    /// The user is looked up on the first call and remembered for the rest of
+    /// the session's life, so repeat calls don't go back to the server. Use
+    /// [`Session::clear_current_user()`] to force a fresh lookup.
+    ///
+    /// Sessions resumed with [`Client::session_from_tokens()`] without a
+    /// [`Login`] don't know who they belong to and will give an
+    /// [`Error::Unexpected`].
+    pub async fn current_user(&self) -> Result<Entity> {
+        // Holding the lock for the duration of the lookup means concurrent
+        // callers will wait for the first lookup rather than racing it.
+        let mut current_user = self.current_user.lock().await;
+        if let Some(user) = current_user.as_ref() {
+            return Ok(user.clone());
+        }
+
+        let (entity, filters) = match self.login.as_ref() {
+            Some(Login::HumanUser(login)) => {
+                ("HumanUser", filters::basic(&[field("login").is(login)]))
+            }
+            Some(Login::ApiUser(script_name)) => (
+                "ApiUser",
+                filters::basic(&[field("firstname").is(script_name)]),
+            ),
+            None => {
+                return Err(Error::Unexpected(String::from(
+                    "Session was not authenticated as a known user.",
+                )))
+            }
+        };
+
+        let resp: PaginatedRecordResponse = self
+            .search(entity, "id", &filters)
+            .size(Some(1))
+            .execute()
+            .await?;
+
+        let user = resp
+            .data
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|record| record.id)
+            .map(|id| Entity::new(entity, id))
+            .ok_or_else(|| Error::NotFound(format!("Could not find current `{}`.", entity)))?;
+
+        *current_user = Some(user.clone());
+        Ok(user)
+    }
+
+    /// Forget the user remembered by [`Session::current_user()`] so the next
+    /// call will look it up again.
+    pub async fn clear_current_user(&self) {
+        *self.current_user.lock().await = None;
+    }
+
+    /// Give an [`Error::Unexpected`] listing any of the comma separated
+    /// `fields` that don't exist on the entity.
+    ///
+    /// Fields on linked entities (ie. `sg_sequence.Sequence.code`) are
+    /// checked at each step, so both the link field and the field on the
+    /// linked entity type must exist.
+    pub(crate) async fn check_fields_exist(&self, entity: &str, fields: &str) -> Result<()> {
+        let mut unknown = vec![];
+        for name in fields
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty() && *name != "*")
+        {
+            let mut parts = name.split('.');
+            let mut entity_type = entity;
+            let mut field = parts.next().unwrap_or_default();
+            loop {
+                if !self.has_field(entity_type, field).await? {
+                    unknown.push(name);
+                    break;
+                }
+                match (parts.next(), parts.next()) {
+                    (Some(linked_type), Some(linked_field)) => {
+                        entity_type = linked_type;
+                        field = linked_field;
+                    }
+                    (None, None) => break,
+                    // A link field and entity type with no field after.
+                    _ => {
+                        unknown.push(name);
+                        break;
+                    }
+                }
+            }
+        }
+
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Unexpected(format!(
+                "Unknown fields for `{}`: `{}`.",
+                entity,
+                unknown.join(", ")
+            )))
+        }
+    }
+
+    /// Check whether a field exists on the entity type.
+    ///
+    /// The field names for each entity type are read from the schema the
+    /// first time they're needed, then remembered for the rest of the
+    /// session's life. Unknown entity types have no fields.
+    async fn has_field(&self, entity: &str, field: &str) -> Result<bool> {
+        if field == "id" || field == "type" {
+            return Ok(true);
         }
-        handle_response(req.send().await?).await
+
+        let mut field_names = self.field_names.lock().await;
+        if !field_names.contains_key(entity) {
+            let names = match self.schema_fields_read(None, entity).await {
+                Ok(resp) => resp.data.unwrap_or_default().into_keys().collect(),
+                Err(Error::NotFound(_)) => HashSet::new(),
+                Err(err) => return Err(err),
+            };
+            field_names.insert(entity.to_string(), names);
+        }
+        Ok(field_names[entity].contains(field))
+    }
+
+    /// Forget the field names remembered for
+    /// [`SearchBuilder::validate_fields()`] so they'll be read from the
+    /// schema again.
+    pub async fn clear_field_names(&self) {
+        self.field_names.lock().await.clear();
+    }
+
+    /// List the custom entity types (`CustomEntity01`, `CustomNonProjectEntity01`,
+    /// etc) enabled on the server, as pairs of type name and display name.
+    ///
+    /// Results are sorted by type name.
+    pub async fn custom_entities(&self, project_id: Option<i32>) -> Result<Vec<(String, String)>> {
+        let resp: SchemaEntitiesResponse = self.schema_read(project_id).await?;
+        Ok(custom_entities_from_schema(resp))
     }
 
     /// Destroy (delete) an entity.
     pub async fn destroy(&self, entity: &str, id: i32) -> Result<()> {
         let (sg, token) = self.get_sg().await?;
-        let url = format!("{}/api/v1/entity/{}/{}", sg.sg_server, entity, id,);
-        let resp = sg
+        let url = format!("{}{}/entity/{}/{}", sg.sg_server, sg.api_base, entity, id,);
+        let req = sg
             .http
             .delete(&url)
             .bearer_auth(token)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+            .header("Accept", "application/json");
+        let resp = sg.send(req).await?;
         if resp.status().is_success() {
             Ok(())
         } else {
@@ -163,24 +614,67 @@ impl<'sg> Session<'sg> {
         }
     }
 
-    /// Provides access to the activity stream of an entity
+    /// Destroy (delete) the given entity.
+    ///
+    /// The same as [`Session::destroy()`], for when the type and id are
+    /// already held together as an [`Entity`].
+    pub async fn destroy_entity(&self, entity: &Entity) -> Result<()> {
+        self.destroy(&entity.r#type, entity.id).await
+    }
+
+    /// Delete several records of the same entity type, reporting the outcome
+    /// for each id.
+    ///
+    /// The deletes are first sent as a single batch request. Batch requests
+    /// are transactional, so when ShotGrid rejects any one of the deletes (for
+    /// example because the record is already gone) nothing is deleted. In that
+    /// case each id is retried with its own [`Session::destroy()`] so the rest
+    /// can still go through.
+    ///
+    /// Any other failure of the batch (a timeout, say) is returned as-is,
+    /// since the deletes may or may not have been applied.
+    ///
+    /// Results are returned in the same order as `ids`.
+    /// <https://developer.shotgridsoftware.com/rest-api/#batch-requests>
+    pub async fn destroy_many(&self, entity: &str, ids: &[i32]) -> Result<Vec<(i32, Result<()>)>> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        match self
+            .send_batch::<_, Value>(&batch_delete_payload(entity, ids))
+            .await
+        {
+            Ok(_) => Ok(ids.iter().map(|id| (*id, Ok(()))).collect()),
+            // A record already gone is reported as `NotFound` rather than as
+            // a `ServerError`.
+            Err(e @ Error::ServerError(_)) | Err(e @ Error::NotFound(_)) => {
+                log::debug!(
+                    "Batch delete rejected, retrying individually. Cause: `{}`.",
+                    e
+                );
+                let results: Vec<_> = stream::iter(ids.iter())
+                    .map(|id| self.destroy(entity, *id))
+                    .buffered(BATCH_RETRY_CONCURRENCY)
+                    .collect()
+                    .await;
+                Ok(ids.iter().copied().zip(results).collect())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Provides access to the activity stream of an entity.
+    ///
+    /// The returned builder can be used to page through the updates, and to
+    /// request extra fields for the entities they reference.
     /// <https://developer.shotgridsoftware.com/rest-api/#read-entity-activity-stream>
-    pub async fn entity_activity_stream_read(
-        &self,
-        entity_type: &str,
+    pub fn entity_activity_stream_read<'a>(
+        &'a self,
+        entity_type: &'a str,
         entity_id: i32,
-    ) -> Result<EntityActivityStreamResponse> {
-        let (sg, token) = self.get_sg().await?;
-        let req = sg
-            .http
-            .get(&format!(
-                "{}/api/v1/entity/{}/{}/activity_stream",
-                sg.sg_server, entity_type, entity_id
-            ))
-            .bearer_auth(token)
-            .header("Accept", "application/json");
-
-        handle_response(req.send().await?).await
+    ) -> EntityActivityStreamReadReqBuilder<'a> {
+        EntityActivityStreamReadReqBuilder::new(self, entity_type, entity_id)
     }
 
     /// Provides the information for where an upload should be sent and how to connect the upload
@@ -207,14 +701,14 @@ impl<'sg> Session<'sg> {
         let req = sg
             .http
             .get(&format!(
-                "{}/api/v1/entity/{}/{}/{}/_upload",
-                sg.sg_server, entity, entity_id, field_name
+                "{}{}/entity/{}/{}/{}/_upload",
+                sg.sg_server, sg.api_base, entity, entity_id, field_name
             ))
             .query(&params)
             .bearer_auth(token)
             .header("Accept", "application/json");
 
-        handle_response(req.send().await?).await
+        sg.send_idempotent(req).await
     }
 
     /// Provide access to information about an image or attachment field. You can optionally
@@ -232,8 +726,8 @@ impl<'sg> Session<'sg> {
         let mut req = sg
             .http
             .get(&format!(
-                "{}/api/v1/entity/{}/{}/{}",
-                sg.sg_server, entity_type, entity_id, field_name
+                "{}{}/entity/{}/{}/{}",
+                sg.sg_server, sg.api_base, entity_type, entity_id, field_name
             ))
             .bearer_auth(token)
             .header("Accept", "application/json");
@@ -246,25 +740,60 @@ impl<'sg> Session<'sg> {
             req = req.header("Range", &val);
         }
 
-        handle_response(req.send().await?).await
+        sg.send_idempotent(req).await
+    }
+
+    /// Download the content of an image or attachment field.
+    ///
+    /// The returned [`FileDownload`] gives the content type and length up
+    /// front (when the server provides them) with the file content available
+    /// as a stream of bytes.
+    ///
+    /// An interrupted download can be resumed by giving a `range` such as
+    /// `bytes=1024-`.
+    /// <https://developer.shotgridsoftware.com/rest-api/#read-file-field>
+    pub async fn entity_file_field_download(
+        &self,
+        entity_type: &str,
+        entity_id: i32,
+        field_name: &str,
+        alt: AltImages,
+        range: Option<String>,
+    ) -> Result<FileDownload> {
+        let (sg, token) = self.get_sg().await?;
+        let mut req = sg
+            .http
+            .get(&format!(
+                "{}{}/entity/{}/{}/{}",
+                sg.sg_server, sg.api_base, entity_type, entity_id, field_name
+            ))
+            .query(&[("alt", alt)])
+            .bearer_auth(token);
+
+        if let Some(val) = range {
+            req = req.header("Range", &val);
+        }
+
+        FileDownload::from_response(sg.send(req).await?).await
     }
 
     /// Provides access to the list of users that follow an entity.
     /// <https://developer.shotgridsoftware.com/rest-api/#read-entity-followers>
-    pub async fn entity_followers_read<D: 'static>(&self, entity: &str, entity_id: i32) -> Result<D>
-    where
-        D: DeserializeOwned,
-    {
+    pub async fn entity_followers_read(
+        &self,
+        entity: &str,
+        entity_id: i32,
+    ) -> Result<ResourceArrayResponse<FollowerRecord, SelfLink>> {
         let (sg, token) = self.get_sg().await?;
         let req = sg
             .http
             .get(&format!(
-                "{}/api/v1/entity/{}/{}/followers",
-                sg.sg_server, entity, entity_id
+                "{}{}/entity/{}/{}/followers",
+                sg.sg_server, sg.api_base, entity, entity_id
             ))
             .bearer_auth(token)
             .header("Accept", "application/json");
-        handle_response(req.send().await?).await
+        sg.send_idempotent(req).await
     }
 
     /// Allows a user to follow one or more entities
@@ -281,14 +810,14 @@ impl<'sg> Session<'sg> {
         let request = sg
             .http
             .post(&format!(
-                "{}/api/v1/entity/human_users/{}/follow",
-                sg.sg_server, user_id
+                "{}{}/entity/human_users/{}/follow",
+                sg.sg_server, sg.api_base, user_id
             ))
             .bearer_auth(token)
             .header("Accept", "application/json")
             .json(&json!({ "entities": entities }));
 
-        handle_response(request.send().await?).await
+        handle_response(sg.send(request).await?).await
     }
 
     /// Provides access to records related to the current entity record via the entity or multi-entity field.
@@ -317,14 +846,14 @@ impl<'sg> Session<'sg> {
         let request = sg
             .http
             .put(&format!(
-                "{}/api/v1/entity/{}/{}/unfollow",
-                sg.sg_server, entity_type, entity_id
+                "{}{}/entity/{}/{}/unfollow",
+                sg.sg_server, sg.api_base, entity_type, entity_id
             ))
             .bearer_auth(token)
             .header("Accept", "application/json")
             .json(&json!({ "user_id": user_id }));
 
-        handle_response(request.send().await?).await
+        handle_response(sg.send(request).await?).await
     }
 
     /// Provides the information for where an upload should be sent and how to connect the upload
@@ -346,14 +875,59 @@ impl<'sg> Session<'sg> {
         let req = sg
             .http
             .get(&format!(
-                "{}/api/v1/entity/{}/{}/_upload",
-                sg.sg_server, entity, entity_id
+                "{}{}/entity/{}/{}/_upload",
+                sg.sg_server, sg.api_base, entity, entity_id
             ))
             .query(&params)
             .bearer_auth(token)
             .header("Accept", "application/json");
 
-        handle_response(req.send().await?).await
+        sg.send_idempotent(req).await
+    }
+
+    /// Read the page at one of the `self`, `next`, or `prev` links from a
+    /// paginated response, as given.
+    ///
+    /// Links may be absolute, or relative to the server (as ShotGrid usually
+    /// gives them, such as `/api/v1/entity/shots?page[number]=2`). Either way
+    /// the query in the link is sent as-is, so pagination can be driven off
+    /// the links instead of rebuilding the original request.
+    ///
+    /// This issues a `GET`, so it suits the links from reading records and
+    /// relationships. The links from a search point back at the `_search`
+    /// endpoint, which needs the filters sent again, so use
+    /// [`SearchBuilder::stream()`](`crate::SearchBuilder::stream()`) for those.
+    ///
+    /// ```no_run
+    /// # use shotgrid_rs::Session;
+    /// # use shotgrid_rs::types::{PaginationLinks, ResourceArrayResponse};
+    /// # use serde_json::Value;
+    /// # async fn example(session: &Session<'_>, resp: ResourceArrayResponse<Value, PaginationLinks>) -> shotgrid_rs::Result<()> {
+    /// if let Some(next) = resp.links.as_ref().and_then(|links| links.next.as_ref()) {
+    ///     let next_page = session.fetch_page::<Value>(next).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_page<R>(
+        &self,
+        link: &str,
+    ) -> Result<ResourceArrayResponse<R, PaginationLinks>>
+    where
+        R: DeserializeOwned,
+    {
+        let (sg, token) = self.get_sg().await?;
+        let url = if link.starts_with("http://") || link.starts_with("https://") {
+            link.to_string()
+        } else {
+            sg.server_link(link)
+        };
+        let req = sg
+            .http
+            .get(&url)
+            .bearer_auth(token)
+            .header("Accept", "application/json");
+        sg.send_idempotent(req).await
     }
 
     /// Apparently this is an internal means for interrogating the navigation
@@ -371,11 +945,14 @@ impl<'sg> Session<'sg> {
         let (sg, token) = self.get_sg().await?;
         let req = sg
             .http
-            .post(&format!("{}/api/v1/hierarchy/_expand", sg.sg_server))
+            .post(&format!(
+                "{}{}/hierarchy/_expand",
+                sg.sg_server, sg.api_base
+            ))
             .bearer_auth(token)
             .header("Accept", "application/json")
             .json(&data);
-        handle_response(req.send().await?).await
+        handle_response(sg.send(req).await?).await
     }
 
     /// Apparently this is an internal means for interrogating the navigation
@@ -393,11 +970,39 @@ impl<'sg> Session<'sg> {
         let (sg, token) = self.get_sg().await?;
         let req = sg
             .http
-            .post(&format!("{}/api/v1/hierarchy/_search", sg.sg_server))
+            .post(&format!(
+                "{}{}/hierarchy/_search",
+                sg.sg_server, sg.api_base
+            ))
             .bearer_auth(token)
             .header("Accept", "application/json")
             .json(&data);
-        handle_response(req.send().await?).await
+        handle_response(sg.send(req).await?).await
+    }
+
+    /// Find all the records of a given entity type last modified by a certain
+    /// `HumanUser` since a given date.
+    ///
+    /// `since` is a date or date time string, such as `"2021-09-11"` or
+    /// `"2021-09-11T20:30:00Z"`.
+    ///
+    /// `fields` is a comma separated list of field names to return for each
+    /// record.
+    ///
+    /// Unlike [`Session::search()`], this will walk *all the pages* of
+    /// results, so be mindful of how wide a net you cast.
+    pub async fn modified_by(
+        &self,
+        entity: &str,
+        user_id: i32,
+        since: &str,
+        fields: &str,
+    ) -> Result<Vec<Record>> {
+        let filters = modified_by_filters(user_id, since);
+        self.search(entity, fields, &filters)
+            .stream()
+            .try_collect()
+            .await
     }
 
     /// Provides the values of a subset of site preferences.
@@ -409,10 +1014,20 @@ impl<'sg> Session<'sg> {
         let (sg, token) = self.get_sg().await?;
         let req = sg
             .http
-            .get(&format!("{}/api/v1/preferences", sg.sg_server))
+            .get(&format!("{}{}/preferences", sg.sg_server, sg.api_base))
             .bearer_auth(token)
             .header("Accept", "application/json");
-        handle_response(req.send().await?).await
+        sg.send_idempotent(req).await
+    }
+
+    /// Read the commonly used site preferences, such as the date and currency
+    /// formats, and the length of the working week.
+    ///
+    /// See [`Session::preferences_read()`] to read the response into a type
+    /// of your own.
+    pub async fn site_preferences(&self) -> Result<SitePreferences> {
+        let resp: SingleResourceResponse<Value, SelfLink> = self.preferences_read().await?;
+        Ok(SitePreferences::from(resp.data.unwrap_or_default()))
     }
 
     /// Update the last access time of a project by a user.
@@ -426,19 +1041,47 @@ impl<'sg> Session<'sg> {
         let req = sg
             .http
             .put(&format!(
-                "{}/api/v1/entity/projects/{}/_update_last_accessed",
-                sg.sg_server, project_id
+                "{}{}/entity/projects/{}/_update_last_accessed",
+                sg.sg_server, sg.api_base, project_id
             ))
             .bearer_auth(token)
             .header("Accept", "application/json")
             .json(&json!({ "user_id": user_id }));
 
-        handle_response(req.send().await?).await
+        handle_response(sg.send(req).await?).await
+    }
+
+    /// Update the last access time of a project by the user this session
+    /// was authenticated as (see [`Session::current_user()`]).
+    ///
+    /// Only `HumanUser`s have a last access time, so this gives an
+    /// [`Error::BadClientConfig`] for sessions from
+    /// [`Client::authenticate_script()`]. Use
+    /// [`Session::project_last_accessed_update()`] to update the time for
+    /// some other user.
+    pub async fn mark_project_accessed(
+        &self,
+        project_id: i32,
+    ) -> Result<ProjectAccessUpdateResponse> {
+        if self.is_api_user() {
+            return Err(Error::BadClientConfig(String::from(
+                "mark_project_accessed requires a HumanUser; use project_last_accessed_update",
+            )));
+        }
+        let user = self.current_user().await?;
+        self.project_last_accessed_update(project_id, user.id).await
     }
 
     /// Read the data for a single entity.
     ///
     /// `fields` is an optional comma separated list of field names to return in the response.
+    ///
+    /// Fields on linked entities can be included using "deep" (dotted) field
+    /// names in the form `link_field.EntityType.field`, for example
+    /// `project.Project.name` to get the name of a `Shot`'s project. These
+    /// come back as attributes of the record under the same dotted name.
+    /// ShotGrid leaves out any fields it doesn't recognize, so take care with
+    /// spelling, or see [`SearchBuilder::validate_fields()`].
     pub async fn read<D: 'static>(&self, entity: &str, id: i32, fields: Option<&str>) -> Result<D>
     where
         D: DeserializeOwned,
@@ -446,50 +1089,135 @@ impl<'sg> Session<'sg> {
         let (sg, token) = self.get_sg().await?;
         let mut req = sg
             .http
-            .get(&format!("{}/api/v1/entity/{}/{}", sg.sg_server, entity, id))
+            .get(&format!(
+                "{}{}/entity/{}/{}",
+                sg.sg_server, sg.api_base, entity, id
+            ))
             .bearer_auth(token)
             .header("Accept", "application/json");
 
         if let Some(fields) = fields {
-            req = req.query(&[("fields", fields)]);
+            req = req.query(&[ReturnFields::parse(fields).query(ReturnFieldsTarget::Read)]);
         }
 
-        handle_response(req.send().await?).await
+        sg.send_idempotent(req).await
     }
-    /// Revive an entity.
-    /// <https://developer.shotgridsoftware.com/rest-api/#revive-a-record>
-    pub async fn revive<D: 'static>(&self, entity: &str, entity_id: i32) -> Result<D>
+
+    /// Read the data for a single entity, reusing the response from an
+    /// earlier read when the record hasn't changed.
+    ///
+    /// Since ShotGrid doesn't support conditional requests, this first reads
+    /// the record's `updated_at` and compares it to the one remembered in
+    /// `cache`. The rest of the `fields` are only read when the record has
+    /// been updated since, or it's not yet in the cache.
+    ///
+    /// Records without an `updated_at` are always read in full. See
+    /// [`ReadCache`] for the limits of relying on `updated_at`.
+    pub async fn read_cached<D>(
+        &self,
+        cache: &ReadCache,
+        entity: &str,
+        id: i32,
+        fields: Option<&str>,
+    ) -> Result<D>
     where
-        D: DeserializeOwned,
+        D: DeserializeOwned + 'static,
     {
-        let (sg, token) = self.get_sg().await?;
-        let req = sg
-            .http
-            .post(&format!(
-                "{}/api/v1/entity/{}/{}?revive=true",
-                sg.sg_server, entity, entity_id
-            ))
-            .bearer_auth(token)
-            .header("Accept", "application/json");
+        let stamp: SingleRecordResponse = self.read(entity, id, Some("updated_at")).await?;
+        let updated_at = match stamp.data.as_ref().and_then(|r| r.attr_str("updated_at")) {
+            Some(updated_at) => updated_at.to_string(),
+            None => return self.read(entity, id, fields).await,
+        };
 
-        handle_response(req.send().await?).await
+        if let Some(body) = cache.get(entity, id, fields, &updated_at).await {
+            return crate::deserialize_value(&body);
+        }
+
+        let body: Value = self.read(entity, id, fields).await?;
+        cache
+            .insert(entity, id, fields, updated_at, body.clone())
+            .await;
+        crate::deserialize_value(&body)
     }
 
-    pub async fn schema_read<D: 'static>(&self, project_id: Option<i32>) -> Result<D>
+    /// Read the data for a single entity, giving `None` when it doesn't exist.
+    ///
+    /// This is the same as [`Session::read()`], except an
+    /// [`Error::NotFound`] is given as `Ok(None)`. All other errors are
+    /// returned as-is.
+    pub async fn read_opt<D>(
+        &self,
+        entity: &str,
+        id: i32,
+        fields: Option<&str>,
+    ) -> Result<Option<D>>
+    where
+        D: DeserializeOwned + 'static,
+    {
+        match self.read(entity, id, fields).await {
+            Ok(data) => Ok(Some(data)),
+            Err(Error::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Read the data for the given entity.
+    ///
+    /// The same as [`Session::read()`], for when the type and id are already
+    /// held together as an [`Entity`].
+    pub async fn read_entity<D>(&self, entity: &Entity, fields: Option<&str>) -> Result<D>
+    where
+        D: DeserializeOwned + 'static,
+    {
+        self.read(&entity.r#type, entity.id, fields).await
+    }
+
+    /// Revive an entity.
+    /// <https://developer.shotgridsoftware.com/rest-api/#revive-a-record>
+    pub async fn revive<D: 'static>(&self, entity: &str, entity_id: i32) -> Result<D>
     where
         D: DeserializeOwned,
     {
         let (sg, token) = self.get_sg().await?;
-        let mut req = sg
+        let req = sg
             .http
-            .get(&format!("{}/api/v1/schema", sg.sg_server))
+            .post(&format!(
+                "{}{}/entity/{}/{}?revive=true",
+                sg.sg_server, sg.api_base, entity, entity_id
+            ))
             .bearer_auth(token)
             .header("Accept", "application/json");
 
+        handle_response(sg.send(req).await?).await
+    }
+
+    /// Revive the given entity.
+    ///
+    /// The same as [`Session::revive()`], for when the type and id are
+    /// already held together as an [`Entity`].
+    pub async fn revive_entity<D>(&self, entity: &Entity) -> Result<D>
+    where
+        D: DeserializeOwned + 'static,
+    {
+        self.revive(&entity.r#type, entity.id).await
+    }
+
+    pub async fn schema_read<D: 'static>(&self, project_id: Option<i32>) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        let (sg, token) = self.get_sg().await?;
+        let mut req = self.localize(
+            sg.http
+                .get(&format!("{}{}/schema", sg.sg_server, sg.api_base))
+                .bearer_auth(token)
+                .header("Accept", "application/json"),
+        );
+
         if let Some(id) = project_id {
             req = req.query(&[("project_id", id)]);
         }
-        handle_response(req.send().await?).await
+        sg.send_idempotent(self.schema_timeout(req)).await
     }
 
     /// Return schema information for the given entity.
@@ -501,16 +1229,45 @@ impl<'sg> Session<'sg> {
         entity: &str,
     ) -> Result<SchemaEntityResponse> {
         let (sg, token) = self.get_sg().await?;
-        let mut req = sg
-            .http
-            .get(&format!("{}/api/v1/schema/{}", sg.sg_server, entity))
-            .bearer_auth(token)
-            .header("Accept", "application/json");
+        let mut req = self.localize(
+            sg.http
+                .get(&format!(
+                    "{}{}/schema/{}",
+                    sg.sg_server, sg.api_base, entity
+                ))
+                .bearer_auth(token)
+                .header("Accept", "application/json"),
+        );
 
         if let Some(id) = project_id {
             req = req.query(&[("project_id", id)]);
         }
-        handle_response(req.send().await?).await
+        sg.send_idempotent(self.schema_timeout(req)).await
+    }
+
+    /// Update the display name of the given entity.
+    ///
+    /// Unlike the field schema endpoints, this one isn't covered by the REST
+    /// API docs, so it may not be available on every ShotGrid server.
+    pub async fn schema_entity_update(
+        &self,
+        entity: &str,
+        display_name: &str,
+    ) -> Result<SchemaEntityResponse> {
+        let (sg, token) = self.get_sg().await?;
+        let body = UpdateEntityRequest {
+            properties: vec![("name", display_name).into()],
+        };
+        let req = sg
+            .http
+            .put(&format!(
+                "{}{}/schema/{}",
+                sg.sg_server, sg.api_base, entity
+            ))
+            .bearer_auth(token)
+            .header("Accept", "application/json")
+            .json(&body);
+        handle_response(sg.send(self.schema_timeout(req)).await?).await
     }
 
     /// Return all schema field information for a given entity.
@@ -523,16 +1280,127 @@ impl<'sg> Session<'sg> {
         entity: &str,
     ) -> Result<SchemaFieldsResponse> {
         let (sg, token) = self.get_sg().await?;
-        let mut req = sg
-            .http
-            .get(&format!("{}/api/v1/schema/{}/fields", sg.sg_server, entity))
-            .bearer_auth(token)
-            .header("Accept", "application/json");
+        let mut req = self.localize(
+            sg.http
+                .get(&format!(
+                    "{}{}/schema/{}/fields",
+                    sg.sg_server, sg.api_base, entity
+                ))
+                .bearer_auth(token)
+                .header("Accept", "application/json"),
+        );
 
         if let Some(id) = project_id {
             req = req.query(&[("project_id", id)]);
         }
-        handle_response(req.send().await?).await
+        sg.send_idempotent(self.schema_timeout(req)).await
+    }
+
+    /// Return the schema fields for a given entity as a map of field name to
+    /// [`FieldInfo`].
+    ///
+    /// This is a convenience over [`Session::schema_fields_read()`] for
+    /// checking a field exists (and what it holds) without digging through
+    /// the raw schema records.
+    pub async fn schema_fields_read_typed(
+        &self,
+        project_id: Option<i32>,
+        entity: &str,
+    ) -> Result<HashMap<String, FieldInfo>> {
+        let resp = self.schema_fields_read(project_id, entity).await?;
+        Ok(resp
+            .data
+            .unwrap_or_default()
+            .iter()
+            .map(|(name, record)| (name.clone(), FieldInfo::from(record)))
+            .collect())
+    }
+
+    /// Return the schema for just the given fields of an entity, keyed by
+    /// field name.
+    ///
+    /// The schema for all the entity's fields is read in one request, rather
+    /// than one request per field as with [`Session::schema_field_read()`].
+    /// Gives an [`Error::Unexpected`] listing any of the fields the entity
+    /// doesn't have.
+    pub async fn schema_fields_subset(
+        &self,
+        project_id: Option<i32>,
+        entity: &str,
+        fields: &[&str],
+    ) -> Result<HashMap<String, SchemaFieldRecord>> {
+        let mut all = self
+            .schema_fields_read(project_id, entity)
+            .await?
+            .data
+            .unwrap_or_default();
+
+        let mut subset = HashMap::with_capacity(fields.len());
+        let mut unknown = vec![];
+        for &name in fields {
+            match all.remove(name) {
+                Some(record) => {
+                    subset.insert(name.to_string(), record);
+                }
+                None if subset.contains_key(name) => {}
+                None => unknown.push(name),
+            }
+        }
+
+        if unknown.is_empty() {
+            Ok(subset)
+        } else {
+            Err(Error::Unexpected(format!(
+                "Unknown fields for `{}`: `{}`.",
+                entity,
+                unknown.join(", ")
+            )))
+        }
+    }
+
+    /// List the values a `status_list` (or `list`) field can hold, with the
+    /// names shown for them.
+    ///
+    /// For `status_list` fields, the colors of the statuses are looked up from
+    /// their `Status` records as well, which costs a second request.
+    ///
+    /// Fields with no valid values give an empty list.
+    pub async fn status_values(&self, entity: &str, field_name: &str) -> Result<Vec<StatusValue>> {
+        let resp = self.schema_field_read(None, entity, field_name).await?;
+        let record = match resp.data {
+            Some(record) => record,
+            None => return Ok(vec![]),
+        };
+        let mut values = StatusValue::from_schema(&record);
+
+        let is_status_list =
+            record.data_type.and_then(|v| v.value) == Some(Value::from("status_list"));
+        if !is_status_list || values.is_empty() {
+            return Ok(values);
+        }
+
+        let codes: Vec<&str> = values.iter().map(|v| v.code.as_str()).collect();
+        let filters = filters::basic(&[field("code").in_(&codes)]);
+        let statuses: PaginatedRecordResponse = self
+            .search("Status", "code,bg_color", &filters)
+            .execute()
+            .await?;
+        let colors: HashMap<String, String> = statuses
+            .data
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|status| {
+                Some((
+                    status.attr_str("code")?.to_string(),
+                    status.attr_str("bg_color")?.to_string(),
+                ))
+            })
+            .collect();
+
+        for value in &mut values {
+            value.color = colors.get(&value.code).cloned();
+        }
+        Ok(values)
     }
 
     /// Create a new field on the given entity
@@ -554,14 +1422,14 @@ impl<'sg> Session<'sg> {
         let req = sg
             .http
             .post(&format!(
-                "{}/api/v1/schema/{}/fields",
-                sg.sg_server, entity_type,
+                "{}{}/schema/{}/fields",
+                sg.sg_server, sg.api_base, entity_type,
             ))
             .bearer_auth(token)
             .header("Accept", "application/json")
             .json(&body);
 
-        handle_response(req.send().await?).await
+        handle_response(sg.send(self.schema_timeout(req)).await?).await
     }
 
     /// Delete a field on a given entity
@@ -569,24 +1437,23 @@ impl<'sg> Session<'sg> {
     pub async fn schema_field_delete(&self, entity_type: &str, field_name: &str) -> Result<()> {
         let (sg, token) = self.get_sg().await?;
         let url = format!(
-            "{}/api/v1/schema/{}/fields/{}",
-            sg.sg_server, entity_type, field_name
+            "{}{}/schema/{}/fields/{}",
+            sg.sg_server, sg.api_base, entity_type, field_name
         );
         let req = sg
             .http
             .delete(&url)
             .bearer_auth(token)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+            .header("Accept", "application/json");
+        let resp = sg.send(self.schema_timeout(req)).await?;
 
-        if req.status().is_success() {
+        if resp.status().is_success() {
             Ok(())
         } else {
             Err(Error::Unexpected(format!(
                 "Server responded to `DELETE {}` with `{}`",
                 &url,
-                req.status()
+                resp.status()
             )))
         }
     }
@@ -596,25 +1463,24 @@ impl<'sg> Session<'sg> {
     pub async fn schema_field_revive(&self, entity_type: &str, field_name: &str) -> Result<()> {
         let (sg, token) = self.get_sg().await?;
         let url = format!(
-            "{}/api/v1/schema/{}/fields/{}?revive=true",
-            sg.sg_server, entity_type, field_name
+            "{}{}/schema/{}/fields/{}?revive=true",
+            sg.sg_server, sg.api_base, entity_type, field_name
         );
 
         let req = sg
             .http
             .post(&url)
             .bearer_auth(token)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+            .header("Accept", "application/json");
+        let resp = sg.send(self.schema_timeout(req)).await?;
 
-        if req.status().is_success() {
+        if resp.status().is_success() {
             Ok(())
         } else {
             Err(Error::Unexpected(format!(
                 "Server responded to `POST {}` with `{}`",
                 &url,
-                req.status()
+                resp.status()
             )))
         }
     }
@@ -629,20 +1495,21 @@ impl<'sg> Session<'sg> {
         field_name: &str,
     ) -> Result<SchemaFieldResponse> {
         let (sg, token) = self.get_sg().await?;
-        let mut req = sg
-            .http
-            .get(&format!(
-                "{}/api/v1/schema/{}/fields/{}",
-                sg.sg_server, entity, field_name,
-            ))
-            .bearer_auth(token)
-            .header("Accept", "application/json");
+        let mut req = self.localize(
+            sg.http
+                .get(&format!(
+                    "{}{}/schema/{}/fields/{}",
+                    sg.sg_server, sg.api_base, entity, field_name,
+                ))
+                .bearer_auth(token)
+                .header("Accept", "application/json"),
+        );
 
         if let Some(id) = project_id {
             req = req.query(&[("project_id", id)]);
         }
 
-        handle_response(req.send().await?).await
+        sg.send_idempotent(self.schema_timeout(req)).await
     }
     /// Update the properties of a field on an entity
     /// <https://developer.shotgridsoftware.com/rest-api/#revive-one-field-from-an-entity>
@@ -664,13 +1531,13 @@ impl<'sg> Session<'sg> {
         let req = sg
             .http
             .put(&format!(
-                "{}/api/v1/schema/{}/fields/{}",
-                sg.sg_server, entity_type, field_name
+                "{}{}/schema/{}/fields/{}",
+                sg.sg_server, sg.api_base, entity_type, field_name
             ))
             .bearer_auth(token)
             .header("Accept", "application/json")
             .json(&body);
-        handle_response(req.send().await?).await
+        handle_response(sg.send(self.schema_timeout(req)).await?).await
     }
 
     /// Find a list of entities matching some filter criteria.
@@ -790,7 +1657,10 @@ impl<'sg> Session<'sg> {
     /// > been acquired with the "sudo as"
     /// > [`Client::authenticate_script_as_user()`] method.
     /// >
-    /// > Failing to supply a valid `HumanUser` for this operation will get you
+    /// > Sessions from [`Client::authenticate_script()`] act as an `ApiUser`,
+    /// > so running a text search with one returns
+    /// > [`Error::BadClientConfig`] without contacting the server. Otherwise,
+    /// > failing to supply a valid `HumanUser` for this operation will get you
     /// > a `500` response from ShotGrid, with a 100 "unknown" error code.
     ///
     /// For details on the filter syntax, please refer to the docs:
@@ -805,61 +1675,183 @@ impl<'sg> Session<'sg> {
     }
 
     /// Provides access to the thread content of an entity. Currently only note is supported.
+    ///
+    /// Each key/value pair in `entity_fields` is sent as a query param, for
+    /// example `entity_fields[Reply]` => `content,user`.
     /// <https://developer.shotgridsoftware.com/rest-api/#read-the-thread-contents-for-a-note>
-    pub async fn thread_contents_read<D: 'static>(
+    pub async fn thread_contents_read(
         &self,
         note_id: i32,
         entity_fields: Option<HashMap<String, String>>,
-    ) -> Result<D>
-    where
-        D: DeserializeOwned,
-    {
+    ) -> Result<EntityThreadContentsResponse> {
         let (sg, token) = self.get_sg().await?;
         let mut req = sg
             .http
             .get(&format!(
-                "{}/api/v1/entity/notes/{}/thread_contents",
-                sg.sg_server, note_id
+                "{}{}/entity/notes/{}/thread_contents",
+                sg.sg_server, sg.api_base, note_id
             ))
             .bearer_auth(token)
             .header("Accept", "application/json");
 
         if let Some(fields) = entity_fields {
-            for (key, value) in fields {
-                req = req.query(&[(json!(key), json!(value))]); // FIXME: should not be jsonified.
+            for (key, value) in &fields {
+                req = req.query(&[(key.as_str(), value.as_str())]);
             }
         }
-        handle_response(req.send().await?).await
+        sg.send_idempotent(req).await
     }
 
     /// Modify an existing entity.
     ///
     /// `data` is used as the request body and as such should be an object with keys and values
     /// corresponding to the fields on the given entity.
-    pub async fn update<D: 'static>(
+    ///
+    /// As with [`Session::create()`], `data` can be a [`Value`] or a struct of
+    /// your own.
+    pub async fn update<B, D: 'static>(
         &self,
         entity: &str,
         id: i32,
-        data: Value,
+        data: &B,
         fields: Option<&str>,
     ) -> Result<D>
     where
+        B: Serialize + ?Sized,
         D: DeserializeOwned,
     {
         let (sg, token) = self.get_sg().await?;
         let mut req = sg
             .http
-            .put(&format!("{}/api/v1/entity/{}/{}", sg.sg_server, entity, id))
+            .put(&format!(
+                "{}{}/entity/{}/{}",
+                sg.sg_server, sg.api_base, entity, id
+            ))
             .bearer_auth(token)
             .header("Accept", "application/json")
-            .json(&data);
+            .json(data);
 
         if let Some(fields) = fields {
-            req = req.query(&[("options[fields]", fields)]);
+            req = req.query(&[ReturnFields::parse(fields).query(ReturnFieldsTarget::Write)]);
+        }
+
+        handle_response(sg.send(req).await?).await
+    }
+
+    /// Modify the given entity.
+    ///
+    /// The same as [`Session::update()`], for when the type and id are
+    /// already held together as an [`Entity`].
+    pub async fn update_entity<B, D>(
+        &self,
+        entity: &Entity,
+        data: &B,
+        fields: Option<&str>,
+    ) -> Result<D>
+    where
+        B: Serialize + ?Sized,
+        D: DeserializeOwned + 'static,
+    {
+        self.update(&entity.r#type, entity.id, data, fields).await
+    }
+
+    /// Add entities to, or remove them from, a multi-entity field without
+    /// replacing the rest of its value.
+    ///
+    /// A regular [`Session::update()`] replaces multi-entity fields
+    /// wholesale, so adding a single link would otherwise mean reading the
+    /// field, modifying it, then writing it back, racing any other tools
+    /// doing the same. With [`MultiEntityMode::Add`] or
+    /// [`MultiEntityMode::Remove`] the server applies the change instead.
+    ///
+    /// The update is sent as a batch request since that's where ShotGrid
+    /// accepts the update mode. The updated record is returned.
+    /// <https://developer.shotgridsoftware.com/rest-api/#batch-requests>
+    pub async fn update_multi_entity<D>(
+        &self,
+        entity: &str,
+        id: i32,
+        field: &str,
+        mode: MultiEntityMode,
+        entities: &[Entity],
+    ) -> Result<D>
+    where
+        D: DeserializeOwned + 'static,
+    {
+        let resp: ResourceArrayResponse<D, SelfLink> = self
+            .send_batch(&multi_entity_update_payload(
+                entity, id, field, mode, entities,
+            ))
+            .await?;
+        resp.data
+            .unwrap_or_default()
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Unexpected(String::from("Batch update returned no records.")))
+    }
+
+    /// Modify an existing entity, configuring the request with a builder.
+    ///
+    /// See [`Session::create_builder()`] for an example.
+    pub fn update_builder<'a, B>(
+        &'a self,
+        entity: &'a str,
+        id: i32,
+        data: &'a B,
+    ) -> UpdateBuilder<'a, B>
+    where
+        B: Serialize + ?Sized,
+    {
+        UpdateBuilder::new(self, entity, id, data)
+    }
+
+    /// Modify an existing entity, but only if it has not been modified since
+    /// `expected_updated_at`.
+    ///
+    /// The record's current `updated_at` is read first and compared to
+    /// `expected_updated_at` (which should be the value exactly as ShotGrid
+    /// reported it, ie `"2021-09-11T20:30:00Z"`). When they match, the update
+    /// is sent as per [`Session::update()`]. When they don't, no update is
+    /// sent and an [`Error::Conflict`] is returned instead.
+    ///
+    /// # Caveats
+    ///
+    /// ShotGrid offers no server-side compare-and-swap, so the check and the
+    /// update are two separate requests. A write from another client landing
+    /// *between* the read and the update will go undetected.
+    ///
+    /// This narrows the window for lost updates, but it cannot close it.
+    pub async fn update_if_unchanged<B, D>(
+        &self,
+        entity: &str,
+        id: i32,
+        data: &B,
+        expected_updated_at: &str,
+        fields: Option<&str>,
+    ) -> Result<D>
+    where
+        B: Serialize + ?Sized,
+        D: DeserializeOwned + 'static,
+    {
+        let current: SingleRecordResponse = self.read(entity, id, Some("updated_at")).await?;
+        let updated_at = current
+            .data
+            .as_ref()
+            .and_then(|record| record.attr_str("updated_at"));
+
+        if updated_at != Some(expected_updated_at) {
+            return Err(Error::Conflict(format!(
+                "`{}` `{}` was updated at `{}`, expected `{}`",
+                entity,
+                id,
+                updated_at.unwrap_or("<unknown>"),
+                expected_updated_at
+            )));
         }
 
-        handle_response(req.send().await?).await
+        self.update(entity, id, data, fields).await
     }
+
     /// Upload attachments and thumbnails for a given entity.
     ///
     /// The `Session::upload()` method will prepare and return a
@@ -1032,23 +2024,171 @@ impl<'sg> Session<'sg> {
         UploadReqBuilder::new(self, entity, id, field, filename)
     }
 
-    /// Provides access to the list of entities a user follows.
-    /// <https://developer.shotgridsoftware.com/rest-api/#read-user-follows>
-    pub async fn user_follows_read<D: 'static>(&self, user_id: i32) -> Result<D>
+    /// Upload a file as the thumbnail for an entity.
+    ///
+    /// This is the same as [`Session::upload()`] with `field` set to `image`,
+    /// but the returned builder leaves out the display name and tags settings,
+    /// which ShotGrid ignores for thumbnails.
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> shotgrid_rs::Result<()> {
+    /// # use shotgrid_rs::Client;
+    /// # let sg = Client::new(
+    /// #     String::from("https://shotgrid.example.com"),
+    /// #     Some("my-shotgrid-api-user"),
+    /// #     Some("**********")
+    /// # )?;
+    /// # let session = sg.authenticate_script().await?;
+    /// use std::fs::File;
+    ///
+    /// let file = File::open("/path/to/paranorman-poster.jpg")?;
+    ///
+    /// session
+    ///     .upload_thumbnail("Asset", 123456, "paranorman-poster.jpg")
+    ///     .send(file)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn upload_thumbnail<'a>(
+        &'a self,
+        entity: &'a str,
+        id: i32,
+        filename: &'a str,
+    ) -> upload::ThumbnailUploadReqBuilder<'a> {
+        upload::ThumbnailUploadReqBuilder::new(self, entity, id, filename)
+    }
+
+    /// Point a file/link field at a file hosted elsewhere, such as in your
+    /// own S3 bucket, rather than uploading it.
+    ///
+    /// This updates the field to hold a web link to `url`, labelled with
+    /// `display_name` when given, so no file content is transferred.
+    /// ShotGrid creates an `Attachment` for the link, which is returned when
+    /// it's included in the response.
+    ///
+    /// This only works for single file/link fields, such as
+    /// `Version.sg_uploaded_movie`. Multi-entity fields of attachments (such
+    /// as `Note.attachments`) can't be set this way.
+    pub async fn link_url_to_field(
+        &self,
+        entity: &str,
+        id: i32,
+        field: &str,
+        url: &str,
+        display_name: Option<&str>,
+    ) -> Result<Option<Entity>> {
+        let mut link = json!({ "url": url, "link_type": "web" });
+        if let Some(name) = display_name {
+            link["name"] = json!(name);
+        }
+        let data = json!({ field: link });
+
+        let resp: SingleRecordResponse = self.update(entity, id, &data, Some(field)).await?;
+        let attachment = resp
+            .data
+            .as_ref()
+            .and_then(|record| record.attributes.as_ref())
+            .and_then(|attrs| attrs.get(field));
+        Ok(match attachment {
+            Some(link) if link["type"] == "Attachment" => link["id"]
+                .as_i64()
+                .map(|id| Entity::new("Attachment", id as i32)),
+            _ => None,
+        })
+    }
+
+    /// Upload several files to the same entity (and field), for example
+    /// attaching a handful of reference images to a `Note`.
+    ///
+    /// Up to `concurrency` uploads will be in flight at a time.
+    ///
+    /// A failed upload does not stop the others. Instead, the outcome for each
+    /// file is returned (in the same order as `files`) for the caller to
+    /// inspect.
+    ///
+    /// For more control over the individual uploads, see [`Session::upload()`].
+    pub async fn upload_many<R>(
+        &self,
+        entity: &str,
+        id: i32,
+        field: Option<&str>,
+        files: Vec<(String, R)>,
+        concurrency: usize,
+    ) -> Vec<UploadResult>
     where
-        D: DeserializeOwned,
+        R: Read + Sync + Send + 'static,
     {
+        let (filenames, readers): (Vec<String>, Vec<R>) = files.into_iter().unzip();
+        stream::iter(filenames.iter().zip(readers))
+            .map(|(filename, reader)| async move {
+                UploadResult {
+                    filename: filename.clone(),
+                    result: self.upload(entity, id, field, filename).send(reader).await,
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Provides access to the list of entities a user follows.
+    /// <https://developer.shotgridsoftware.com/rest-api/#read-user-follows>
+    pub async fn user_follows_read(
+        &self,
+        user_id: i32,
+    ) -> Result<ResourceArrayResponse<FollowRecord, SelfLink>> {
         let (sg, token) = self.get_sg().await?;
         let req = sg
             .http
             .get(&format!(
-                "{}/api/v1/entity/human_users/{}/following",
-                sg.sg_server, user_id
+                "{}{}/entity/human_users/{}/following",
+                sg.sg_server, sg.api_base, user_id
             ))
             .bearer_auth(token)
             .header("Accept", "application/json");
 
-        handle_response(req.send().await?).await
+        sg.send_idempotent(req).await
+    }
+
+    /// Read the work day rules for several users at once, keyed by user id.
+    ///
+    /// The requests for each user are sent concurrently.
+    pub async fn work_day_rules_for_users(
+        &self,
+        start_date: &str,
+        end_date: &str,
+        user_ids: &[i32],
+        project_id: Option<i32>,
+    ) -> Result<HashMap<i32, Vec<WorkDayRulesData>>> {
+        let reqs = user_ids.iter().map(|&user_id| async move {
+            let resp: GetWorkDayRulesResponse = self
+                .work_days_rules_read(start_date, end_date, project_id, Some(user_id))
+                .await?;
+            Ok::<_, Error>((user_id, resp.data.unwrap_or_default()))
+        });
+        Ok(try_join_all(reqs).await?.into_iter().collect())
+    }
+
+    /// Update the work day rules for a given date.
+    /// <https://developer.shotgridsoftware.com/rest-api/#update-one-work-day-rule>
+    pub async fn work_day_rules_update(
+        &self,
+        data: UpdateWorkDayRulesRequest,
+    ) -> Result<UpdateWorkDayRulesResponse> {
+        let (sg, token) = self.get_sg().await?;
+        let req = sg
+            .http
+            .put(&format!(
+                "{}{}/schedule/work_day_rules",
+                sg.sg_server, sg.api_base
+            ))
+            .bearer_auth(token)
+            .header("Accept", "application/json")
+            .json(&data);
+
+        handle_response(sg.send(req).await?).await
     }
 
     /// Read the work day rules for each day specified in the query.
@@ -1066,7 +2206,10 @@ impl<'sg> Session<'sg> {
         let (sg, token) = self.get_sg().await?;
         let mut req = sg
             .http
-            .get(&format!("{}/api/v1/schedule/work_day_rules", sg.sg_server))
+            .get(&format!(
+                "{}{}/schedule/work_day_rules",
+                sg.sg_server, sg.api_base
+            ))
             .query(&[("start_date", start_date), ("end_date", end_date)])
             .bearer_auth(token)
             .header("Accept", "application/json");
@@ -1079,10 +2222,102 @@ impl<'sg> Session<'sg> {
             req = req.query(&[("user_id", uid)])
         }
 
-        handle_response(req.send().await?).await
+        sg.send_idempotent(req).await
     }
 }
 
+/// Build the body for a batch request with one create per record.
+fn batch_create_payload(entity: &str, records: Vec<Value>, fields: Option<&str>) -> Value {
+    let requests: Vec<Value> = records
+        .into_iter()
+        .map(|data| {
+            let mut req = json!({
+                "request_type": "create",
+                "entity": entity,
+                "data": data,
+            });
+            if let Some(fields) = fields {
+                req["options"] = ReturnFields::parse(fields).batch_options();
+            }
+            req
+        })
+        .collect();
+    json!({ "requests": requests })
+}
+
+fn multi_entity_update_payload(
+    entity: &str,
+    id: i32,
+    field: &str,
+    mode: MultiEntityMode,
+    entities: &[Entity],
+) -> Value {
+    json!({
+        "requests": [
+            {
+                "request_type": "update",
+                "entity": entity,
+                "record_id": id,
+                "data": { field: entities },
+                "multi_entity_update_mode": { field: mode },
+            }
+        ]
+    })
+}
+
+fn batch_delete_payload(entity: &str, ids: &[i32]) -> Value {
+    let requests: Vec<Value> = ids
+        .iter()
+        .map(|id| {
+            json!({
+                "request_type": "delete",
+                "entity": entity,
+                "record_id": id,
+            })
+        })
+        .collect();
+    json!({ "requests": requests })
+}
+
+/// The current unix timestamp, in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Filters for records last updated by a given `HumanUser` after `since`.
+fn modified_by_filters(user_id: i32, since: &str) -> FinalizedFilters {
+    filters::basic(&[
+        field("updated_by").is(EntityRef::new("HumanUser", user_id)),
+        field("updated_at").greater_than(since),
+    ])
+}
+
+/// Pick out the visible custom entity types from a schema read.
+fn custom_entities_from_schema(resp: SchemaEntitiesResponse) -> Vec<(String, String)> {
+    let value = |v: Option<SchemaResponseValue>| v.and_then(|v| v.value);
+
+    let mut entities: Vec<(String, String)> = resp
+        .data
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(entity_type, _)| entity_type.starts_with("Custom"))
+        .filter_map(|(entity_type, record)| {
+            if value(record.visible).and_then(|v| v.as_bool()) != Some(true) {
+                return None;
+            }
+            let name = value(record.name)
+                .and_then(|v| v.as_str().map(String::from))
+                .unwrap_or_else(|| entity_type.clone());
+            Some((entity_type, name))
+        })
+        .collect();
+    entities.sort();
+    entities
+}
+
 #[cfg(test)]
 mod mock_tests {
 
@@ -1216,6 +2451,53 @@ mod mock_tests {
         assert_eq!(true, session.token_expiring().await);
     }
 
+    #[tokio::test]
+    async fn test_session_without_auto_refresh() {
+        let mock_server = MockServer::start().await;
+
+        // Expiry is set to SLOP - 5, so the token would normally be refreshed
+        // before the read.
+        let body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 85,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            // The initial login only, no refresh.
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/1001"))
+            .respond_with(
+                ResponseTemplate::new(401).set_body_raw(TOKEN_EXPIRED, "application/json"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let mut session = sg
+            .authenticate_user("nbabcock", "forgot my passwd")
+            .await
+            .unwrap();
+        session.set_auto_refresh(false);
+
+        match session.read::<Value>("Shot", 1001, None).await {
+            Err(Error::Unauthorized { errors, .. }) => {
+                assert_eq!(errors[0].detail.as_deref(), Some("Token Expired"));
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
     #[tokio::test]
     async fn test_session_can_estimate_zero_expiry() {
         let mock_server = MockServer::start().await;
@@ -1245,4 +2527,2054 @@ mod mock_tests {
 
         assert_eq!(true, session.token_expiring().await);
     }
+
+    #[test]
+    fn test_modified_by_filters() {
+        let filters = modified_by_filters(456, "2021-09-11T00:00:00Z");
+        let expected = json!([
+            ["updated_by", "is", { "type": "HumanUser", "id": 456 }],
+            ["updated_at", "greater_than", "2021-09-11T00:00:00Z"],
+        ]);
+        assert_eq!(&expected, &json!(filters));
+    }
+
+    #[tokio::test]
+    async fn test_session_from_tokens_fresh() {
+        let sg = Client::new(String::from("https://shotgrid.example.com"), None, None).unwrap();
+        let tokens = TokenResponse {
+            token_type: String::from("Bearer"),
+            access_token: String::from("$$ACCESS_TOKEN$$"),
+            expires_in: 600,
+            refresh_token: String::from("$$REFRESH_TOKEN$$"),
+        };
+
+        let session = sg.session_from_tokens(
+            tokens,
+            None,
+            Some(Login::ApiUser(String::from("asset-publisher"))),
+        );
+
+        assert_eq!(false, session.token_expiring().await);
+        assert_eq!(
+            session.login(),
+            Some(&Login::ApiUser(String::from("asset-publisher")))
+        );
+        assert!(session.is_api_user());
+    }
+
+    #[tokio::test]
+    async fn test_session_from_tokens_stale() {
+        let sg = Client::new(String::from("https://shotgrid.example.com"), None, None).unwrap();
+        let tokens = TokenResponse {
+            token_type: String::from("Bearer"),
+            access_token: String::from("$$ACCESS_TOKEN$$"),
+            expires_in: 600,
+            refresh_token: String::from("$$REFRESH_TOKEN$$"),
+        };
+        // Tokens acquired 10 minutes ago should already be considered expiring.
+        let acquired_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - 600;
+
+        let session = sg.session_from_tokens(tokens, Some(acquired_at), None);
+
+        assert_eq!(true, session.token_expiring().await);
+    }
+
+    #[tokio::test]
+    async fn test_reauthenticate() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$NEW_ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$NEW_REFRESH_TOKEN$$"
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .and(BodyContains("grant_type=refresh"))
+            .and(BodyContains("refresh_token=%24%24REFRESH_TOKEN%24%24"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .reauthenticate(
+                "$$REFRESH_TOKEN$$",
+                Some(Login::ApiUser(String::from("asset-publisher"))),
+            )
+            .await
+            .unwrap();
+        assert!(session.is_api_user());
+
+        let tokens = session.current_tokens().await;
+        assert_eq!(tokens.access_token, "$$NEW_ACCESS_TOKEN$$");
+        assert_eq!(tokens.refresh_token, "$$NEW_REFRESH_TOKEN$$");
+        assert_eq!(false, session.token_expiring().await);
+    }
+
+    #[test]
+    fn test_token_expiring_tracks_latest_refresh() {
+        let tokens = || TokenResponse {
+            token_type: String::from("Bearer"),
+            access_token: String::from("$$ACCESS_TOKEN$$"),
+            expires_in: 600,
+            refresh_token: String::from("$$REFRESH_TOKEN$$"),
+        };
+        let ttl = 600 - TOKEN_REFRESH_SLOP;
+
+        let mut state = TokenState {
+            tokens: tokens(),
+            last_refresh: 1000,
+        };
+        assert!(!state.expiring(1000 + ttl));
+        assert!(state.expiring(1000 + ttl + 1));
+
+        // First refresh, right as the initial tokens were expiring.
+        let first = 1000 + ttl + 1;
+        state.refreshed(tokens(), first);
+        assert!(!state.expiring(first + 1));
+        assert!(!state.expiring(first + ttl));
+        assert!(state.expiring(first + ttl + 1));
+
+        // Second refresh, well after the first.
+        let second = first + ttl + 60;
+        state.refreshed(tokens(), second);
+        assert!(!state.expiring(second + ttl));
+        assert!(state.expiring(second + ttl + 1));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_refresh_only_refreshes_once() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$NEW_ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$NEW_REFRESH_TOKEN$$"
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let tokens = TokenResponse {
+            token_type: String::from("Bearer"),
+            access_token: String::from("$$ACCESS_TOKEN$$"),
+            expires_in: 600,
+            refresh_token: String::from("$$REFRESH_TOKEN$$"),
+        };
+        // Already expired, so the next request will need to refresh.
+        let session = sg.session_from_tokens(tokens, Some(0), None);
+
+        let (a, b) = futures::join!(session.get_sg(), session.get_sg());
+        assert_eq!(a.unwrap().1, "$$NEW_ACCESS_TOKEN$$");
+        assert_eq!(b.unwrap().1, "$$NEW_ACCESS_TOKEN$$");
+        assert_eq!(false, session.token_expiring().await);
+    }
+
+    #[tokio::test]
+    async fn test_update_if_unchanged_conflict() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let read_body = r##"
+        {
+          "data": {
+            "id": 123,
+            "type": "Shot",
+            "attributes": { "updated_at": "2021-09-11T20:30:00Z" },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/shots/123" }
+          },
+          "links": { "self": "/api/v1/entity/shots/123" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/123"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(read_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        // The update should never be sent when the record has changed.
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/entity/Shot/123"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let resp = session
+            .update_if_unchanged::<_, Value>(
+                "Shot",
+                123,
+                &json!({"sg_status_list": "ip"}),
+                "2021-09-10T08:00:00Z",
+                None,
+            )
+            .await;
+
+        match resp {
+            Err(Error::Conflict(_)) => {}
+            other => panic!("Expected Error::Conflict, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_if_unchanged_match() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let read_body = r##"
+        {
+          "data": {
+            "id": 123,
+            "type": "Shot",
+            "attributes": { "updated_at": "2021-09-11T20:30:00Z" },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/shots/123" }
+          },
+          "links": { "self": "/api/v1/entity/shots/123" }
+        }
+        "##;
+
+        let update_body = r##"
+        {
+          "data": {
+            "id": 123,
+            "type": "Shot",
+            "attributes": { "sg_status_list": "ip" },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/shots/123" }
+          },
+          "links": { "self": "/api/v1/entity/shots/123" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/123"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(read_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/entity/Shot/123"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(update_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        session
+            .update_if_unchanged::<_, Value>(
+                "Shot",
+                123,
+                &json!({"sg_status_list": "ip"}),
+                "2021-09-11T20:30:00Z",
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_current_user_is_cached() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let search_body = r##"
+        {
+          "data": [
+            {
+              "id": 42,
+              "type": "HumanUser",
+              "attributes": {},
+              "relationships": {},
+              "links": { "self": "/api/v1/entity/human_users/42" }
+            }
+          ],
+          "links": { "self": "/api/v1/entity/human_users/_search" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/HumanUser/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(search_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let first = session.current_user().await.unwrap();
+        let second = session.current_user().await.unwrap();
+        assert_eq!(first.id, 42);
+        assert_eq!(first.r#type, "HumanUser");
+        assert_eq!(second.id, first.id);
+    }
+
+    #[tokio::test]
+    async fn test_mark_project_accessed() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let search_body = r##"
+        {
+          "data": [
+            {
+              "id": 42,
+              "type": "HumanUser",
+              "attributes": {},
+              "relationships": {},
+              "links": { "self": "/api/v1/entity/human_users/42" }
+            }
+          ],
+          "links": { "self": "/api/v1/entity/human_users/_search" }
+        }
+        "##;
+
+        let update_body = r##"
+        {
+          "data": { "id": 70, "type": "Project" },
+          "links": { "self": "/api/v1/entity/projects/70" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/HumanUser/_search"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(search_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/entity/projects/70/_update_last_accessed"))
+            .and(BodyContains(r#""user_id":42"#))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(update_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), Some("my-api-user"), Some("xxxx")).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+        let resp = session.mark_project_accessed(70).await.unwrap();
+        assert_eq!(resp.data.unwrap().id, 70);
+
+        let session = sg.authenticate_script().await.unwrap();
+        assert!(matches!(
+            session.mark_project_accessed(70).await,
+            Err(Error::BadClientConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_current_user_unknown() {
+        let sg = Client::new(String::from("http://localhost"), None, None).unwrap();
+        let session = sg.session_from_tokens(
+            TokenResponse {
+                token_type: String::from("Bearer"),
+                access_token: String::from("$$ACCESS_TOKEN$$"),
+                expires_in: 600,
+                refresh_token: String::from("$$REFRESH_TOKEN$$"),
+            },
+            None,
+            None,
+        );
+        assert!(matches!(
+            session.current_user().await,
+            Err(Error::Unexpected(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_current_tokens() {
+        let sg = Client::new(String::from("http://localhost"), None, None).unwrap();
+        let session = sg.session_from_tokens(
+            TokenResponse {
+                token_type: String::from("Bearer"),
+                access_token: String::from("$$ACCESS_TOKEN$$"),
+                expires_in: 600,
+                refresh_token: String::from("$$REFRESH_TOKEN$$"),
+            },
+            None,
+            None,
+        );
+        let tokens = session.current_tokens().await;
+        assert_eq!(tokens.access_token, "$$ACCESS_TOKEN$$");
+        assert_eq!(tokens.refresh_token, "$$REFRESH_TOKEN$$");
+        assert_eq!(
+            serde_json::to_value(&tokens).unwrap(),
+            json!({
+                "token_type": "Bearer",
+                "access_token": "$$ACCESS_TOKEN$$",
+                "expires_in": 600,
+                "refresh_token": "$$REFRESH_TOKEN$$",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_work_day_rules_for_users() {
+        use wiremock::matchers::query_param;
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let user_1_body = r##"
+        {
+          "data": [
+            { "date": "2021-01-04", "working": true, "description": null, "reason": "STUDIO_WORK_WEEK" },
+            { "date": "2021-01-05", "working": true, "description": null, "reason": "STUDIO_WORK_WEEK" }
+          ],
+          "links": { "self": "/api/v1/schedule/work_day_rules" }
+        }
+        "##;
+
+        let user_2_body = r##"
+        {
+          "data": [
+            { "date": "2021-01-04", "working": false, "description": "Vacation", "reason": "USER_EXCEPTION" },
+            { "date": "2021-01-05", "working": true, "description": null, "reason": "STUDIO_WORK_WEEK" }
+          ],
+          "links": { "self": "/api/v1/schedule/work_day_rules" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schedule/work_day_rules"))
+            .and(query_param("user_id", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(user_1_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schedule/work_day_rules"))
+            .and(query_param("user_id", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(user_2_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let rules = session
+            .work_day_rules_for_users("2021-01-04", "2021-01-05", &[1, 2], None)
+            .await
+            .unwrap();
+
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[&1].len(), 2);
+        assert!(rules[&1].iter().all(|day| day.working == Some(true)));
+        assert_eq!(rules[&2][0].working, Some(false));
+        assert_eq!(rules[&2][0].description.as_deref(), Some("Vacation"));
+    }
+
+    #[test]
+    fn test_custom_entities_from_schema() {
+        let resp: SchemaEntitiesResponse = serde_json::from_value(json!({
+            "data": {
+                "Asset": {
+                    "name": { "value": "Asset", "editable": true },
+                    "visible": { "value": true, "editable": true }
+                },
+                "CustomEntity02": {
+                    "name": { "value": "Vehicle", "editable": true },
+                    "visible": { "value": true, "editable": true }
+                },
+                "CustomEntity01": {
+                    "name": { "value": "Prop Kit", "editable": true },
+                    "visible": { "value": true, "editable": true }
+                },
+                "CustomEntity03": {
+                    "name": { "value": "Custom Entity03", "editable": true },
+                    "visible": { "value": false, "editable": true }
+                },
+                "CustomNonProjectEntity01": {
+                    "name": { "value": "Vendor", "editable": true },
+                    "visible": { "value": true, "editable": true }
+                }
+            },
+            "links": { "self": "/api/v1/schema" }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            custom_entities_from_schema(resp),
+            vec![
+                (String::from("CustomEntity01"), String::from("Prop Kit")),
+                (String::from("CustomEntity02"), String::from("Vehicle")),
+                (
+                    String::from("CustomNonProjectEntity01"),
+                    String::from("Vendor")
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_work_day_rules_update() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let update_body = r##"
+        {
+          "data": {
+            "date": "2021-01-04",
+            "working": false,
+            "description": "Studio closed",
+            "reason": "STUDIO_EXCEPTION"
+          },
+          "links": { "self": "/api/v1/schedule/work_day_rules" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/schedule/work_day_rules"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(update_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let resp = session
+            .work_day_rules_update(UpdateWorkDayRulesRequest {
+                date: String::from("2021-01-04"),
+                working: false,
+                user_id: None,
+                project_id: None,
+                recalculate_field: None,
+                description: Some(String::from("Studio closed")),
+            })
+            .await
+            .unwrap();
+
+        let data = resp.data.unwrap();
+        assert_eq!(data.working, Some(false));
+        assert_eq!(data.reason.as_deref(), Some("STUDIO_EXCEPTION"));
+    }
+
+    #[tokio::test]
+    async fn test_thread_contents_read_query() {
+        use wiremock::matchers::query_param;
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let thread_body = r##"
+        {
+          "data": [
+            {
+              "id": 123,
+              "type": "Note",
+              "content": "Needs more fog.",
+              "created_at": "2021-01-04T18:00:00Z"
+            },
+            {
+              "id": 456,
+              "type": "Reply",
+              "content": "Fog added.",
+              "created_at": "2021-01-05T18:00:00Z"
+            }
+          ],
+          "links": { "self": "/api/v1/entity/notes/123/thread_contents" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/notes/123/thread_contents"))
+            .and(query_param("entity_fields[Reply]", "content,user"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(thread_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let mut entity_fields = HashMap::new();
+        entity_fields.insert(
+            String::from("entity_fields[Reply]"),
+            String::from("content,user"),
+        );
+
+        let resp = session
+            .thread_contents_read(123, Some(entity_fields))
+            .await
+            .unwrap();
+
+        let data = resp.data.unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[1].r#type.as_deref(), Some("Reply"));
+        assert_eq!(data[1].content.as_deref(), Some("Fog added."));
+    }
+
+    #[test]
+    fn test_batch_create_payload() {
+        let payload = batch_create_payload(
+            "Shot",
+            vec![json!({"code": "sh010"}), json!({"code": "sh020"})],
+            Some("id,code"),
+        );
+        assert_eq!(
+            payload,
+            json!({
+                "requests": [
+                    {
+                        "request_type": "create",
+                        "entity": "Shot",
+                        "data": {"code": "sh010"},
+                        "options": {"fields": "id,code"}
+                    },
+                    {
+                        "request_type": "create",
+                        "entity": "Shot",
+                        "data": {"code": "sh020"},
+                        "options": {"fields": "id,code"}
+                    }
+                ]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_many() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let batch_body = r##"
+        {
+          "data": [
+            {
+              "id": 1001,
+              "type": "Shot",
+              "attributes": { "code": "sh010" },
+              "relationships": {},
+              "links": { "self": "/api/v1/entity/shots/1001" }
+            },
+            {
+              "id": 1002,
+              "type": "Shot",
+              "attributes": { "code": "sh020" },
+              "relationships": {},
+              "links": { "self": "/api/v1/entity/shots/1002" }
+            }
+          ]
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/_batch"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(batch_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let created: Vec<Record> = session
+            .create_many(
+                "Shot",
+                vec![json!({"code": "sh010"}), json!({"code": "sh020"})],
+                Some("code"),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(created.len(), 2);
+        assert_eq!(created[0].id, Some(1001));
+        assert_eq!(created[1].id, Some(1002));
+    }
+
+    #[tokio::test]
+    async fn test_read_cached() {
+        use wiremock::matchers::query_param;
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let stamp_body = r##"
+        {
+          "data": {
+            "id": 1001,
+            "type": "Shot",
+            "attributes": { "updated_at": "2021-03-04T05:06:07Z" },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/shots/1001" }
+          },
+          "links": { "self": "/api/v1/entity/shots/1001" }
+        }
+        "##;
+
+        let read_body = r##"
+        {
+          "data": {
+            "id": 1001,
+            "type": "Shot",
+            "attributes": { "code": "sh010" },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/shots/1001" }
+          },
+          "links": { "self": "/api/v1/entity/shots/1001" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/1001"))
+            .and(query_param("fields", "updated_at"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(stamp_body, "application/json"))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/1001"))
+            .and(query_param("fields", "code"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(read_body, "application/json"))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let cache = ReadCache::new();
+        for _ in 0..2 {
+            let resp: SingleRecordResponse = session
+                .read_cached(&cache, "Shot", 1001, Some("code"))
+                .await
+                .unwrap();
+            assert_eq!(resp.data.unwrap().attr_str("code"), Some("sh010"));
+        }
+        assert_eq!(cache.len().await, 1);
+
+        cache.invalidate("Shot", 1001).await;
+        assert!(cache.is_empty().await);
+        let _: SingleRecordResponse = session
+            .read_cached(&cache, "Shot", 1001, Some("code"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_read_opt() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let read_body = r##"
+        {
+          "data": {
+            "id": 1001,
+            "type": "Shot",
+            "attributes": { "code": "sh010" },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/shots/1001" }
+          },
+          "links": { "self": "/api/v1/entity/shots/1001" }
+        }
+        "##;
+
+        let not_found_body = r##"
+        {
+          "errors": [
+            {
+              "id": "xxxxx",
+              "status": 404,
+              "code": 104,
+              "title": "Record not found",
+              "detail": "Shot 1002 not found",
+              "source": null,
+              "meta": null
+            }
+          ]
+        }
+        "##;
+
+        let server_error_body = r##"
+        {
+          "errors": [
+            {
+              "id": "xxxxx",
+              "status": 500,
+              "code": 100,
+              "title": "Internal error",
+              "detail": null,
+              "source": null,
+              "meta": null
+            }
+          ]
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/1001"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(read_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/1002"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_raw(not_found_body, "application/json"),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/1003"))
+            .respond_with(
+                ResponseTemplate::new(500).set_body_raw(server_error_body, "application/json"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let found: Option<SingleRecordResponse> =
+            session.read_opt("Shot", 1001, Some("code")).await.unwrap();
+        assert_eq!(found.unwrap().data.unwrap().id, Some(1001));
+
+        let missing: Option<SingleRecordResponse> =
+            session.read_opt("Shot", 1002, None).await.unwrap();
+        assert!(missing.is_none());
+
+        let err = session
+            .read_opt::<SingleRecordResponse>("Shot", 1003, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ServerError(_)));
+    }
+
+    #[test]
+    fn test_multi_entity_update_payload() {
+        assert_eq!(
+            multi_entity_update_payload(
+                "Shot",
+                1001,
+                "assets",
+                MultiEntityMode::Add,
+                &[Entity::new("Asset", 12)],
+            ),
+            json!({
+                "requests": [
+                    {
+                        "request_type": "update",
+                        "entity": "Shot",
+                        "record_id": 1001,
+                        "data": { "assets": [{ "type": "Asset", "id": 12 }] },
+                        "multi_entity_update_mode": { "assets": "add" }
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_batch_delete_payload() {
+        assert_eq!(
+            batch_delete_payload("Shot", &[1001, 1002]),
+            json!({
+                "requests": [
+                    { "request_type": "delete", "entity": "Shot", "record_id": 1001 },
+                    { "request_type": "delete", "entity": "Shot", "record_id": 1002 }
+                ]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_entity_followers_read() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let followers_body = r##"
+        {
+          "data": [
+            {
+              "id": 88,
+              "type": "HumanUser",
+              "attributes": { "name": "Nigel Babcock" },
+              "links": { "self": "/api/v1/entity/human_users/88" }
+            }
+          ],
+          "links": { "self": "/api/v1/entity/shots/123/followers" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/123/followers"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(followers_body, "application/json"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let followers = session
+            .entity_followers_read("Shot", 123)
+            .await
+            .unwrap()
+            .data
+            .unwrap();
+
+        assert_eq!(followers.len(), 1);
+        assert_eq!(followers[0].id, Some(88));
+        assert_eq!(
+            followers[0].attributes.as_ref().unwrap()["name"],
+            "Nigel Babcock"
+        );
+    }
+
+    /// Matches requests whose body contains the given text.
+    struct BodyContains(&'static str);
+
+    impl wiremock::Match for BodyContains {
+        fn matches(&self, request: &wiremock::Request) -> bool {
+            String::from_utf8_lossy(&request.body).contains(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_each_partial() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let rejected_body = r##"
+        {
+          "errors": [
+            {
+              "id": "xxxxx",
+              "status": 400,
+              "code": 103,
+              "title": "Invalid field",
+              "detail": "sg_nonexistent is not a valid field",
+              "source": null,
+              "meta": null
+            }
+          ]
+        }
+        "##;
+
+        let created_body = r##"
+        {
+          "data": [
+            {
+              "id": 1001,
+              "type": "Shot",
+              "attributes": {},
+              "relationships": {},
+              "links": { "self": "/api/v1/entity/shots/1001" }
+            }
+          ]
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        // The whole batch, and the bad request on its own, are rejected.
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/_batch"))
+            .and(BodyContains("sg_nonexistent"))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_raw(rejected_body, "application/json"),
+            )
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/_batch"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(created_body, "application/json"))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let requests = vec![
+            json!({"request_type": "create", "entity": "Shot", "data": {"code": "sh010"}}),
+            json!({"request_type": "create", "entity": "Shot", "data": {"sg_nonexistent": 1}}),
+            json!({"request_type": "create", "entity": "Shot", "data": {"code": "sh030"}}),
+        ];
+        let results = session.batch_each(requests).await.unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap()["id"], 1001);
+        match &results[1] {
+            Err(Error::ServerError(errors)) => assert_eq!(errors[0].code, Some(103)),
+            other => panic!("Expected ServerError, got {:?}", other),
+        }
+        assert!(results[2].is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_destroy_many_partial() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let not_found_body = r##"
+        {
+          "errors": [
+            {
+              "id": "xxxxx",
+              "status": 404,
+              "code": 104,
+              "title": "Record not found",
+              "detail": null,
+              "source": null,
+              "meta": null
+            }
+          ]
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/_batch"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_raw(not_found_body, "application/json"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v1/entity/Shot/1001"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v1/entity/Shot/1002"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_raw(not_found_body, "application/json"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let results = session.destroy_many("Shot", &[1001, 1002]).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, 1001);
+        assert!(results[0].1.is_ok());
+        assert_eq!(results[1].0, 1002);
+        assert!(results[1].1.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_destroy_many_unauthorized() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/_batch"))
+            .respond_with(
+                ResponseTemplate::new(401).set_body_raw(TOKEN_INVALID, "application/json"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        // Only rejections are retried one at a time.
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let err = session
+            .destroy_many("Shot", &[1001, 1002])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Unauthorized { .. }), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_destroy_many_batched() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/_batch"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(r#"{"data": [true, true]}"#, "application/json"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let results = session.destroy_many("Shot", &[1001, 1002]).await.unwrap();
+
+        assert_eq!(
+            vec![1001, 1002],
+            results
+                .iter()
+                .filter(|(_, res)| res.is_ok())
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_locale_schema_read() {
+        use wiremock::matchers::header;
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let schema_body = r##"
+        {
+          "data": {
+            "name": { "value": "ショット", "editable": false },
+            "visible": { "value": true, "editable": false }
+          },
+          "links": { "self": "/api/v1/schema/Shot" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schema/Shot"))
+            .and(header("Accept-Language", "ja"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(schema_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap()
+            .with_locale("ja");
+
+        let resp = session.schema_entity_read(None, "Shot").await.unwrap();
+        let name = resp.data.unwrap().name.unwrap().value.unwrap();
+        assert_eq!(name, "ショット");
+    }
+
+    #[tokio::test]
+    async fn test_entity_file_field_download_range() {
+        use futures::TryStreamExt;
+        use wiremock::matchers::{header, query_param};
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Asset/123/sg_reference"))
+            .and(query_param("alt", "original"))
+            .and(header("Range", "bytes=4-"))
+            .respond_with(ResponseTemplate::new(206).set_body_raw("456789", "image/png"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let download = session
+            .entity_file_field_download(
+                "Asset",
+                123,
+                "sg_reference",
+                AltImages::Original,
+                Some(String::from("bytes=4-")),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(download.content_type.as_deref(), Some("image/png"));
+        assert_eq!(download.content_length, Some(6));
+
+        let chunks: Vec<_> = download.stream.try_collect().await.unwrap();
+        assert_eq!(chunks.concat(), b"456789");
+    }
+
+    #[tokio::test]
+    async fn test_read_deep_field() {
+        use wiremock::matchers::query_param;
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let record_body = r##"
+        {
+          "data": {
+            "id": 1001,
+            "type": "Shot",
+            "attributes": { "code": "sh010", "project.Project.name": "ParaNorman" },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/shots/1001" }
+          },
+          "links": { "self": "/api/v1/entity/shots/1001" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/1001"))
+            .and(query_param("fields", "code,project.Project.name"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(record_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let resp: SingleRecordResponse = session
+            .read("Shot", 1001, Some("code,project.Project.name"))
+            .await
+            .unwrap();
+        let record = resp.data.unwrap();
+        assert_eq!(record.attr_str("project.Project.name"), Some("ParaNorman"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page() {
+        use wiremock::matchers::query_param;
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let page_body = r##"
+        {
+          "data": [{ "id": 1002, "type": "Shot" }],
+          "links": {
+            "self": "/api/v1/entity/shots?page[number]=2&page[size]=1",
+            "next": "/api/v1/entity/shots?page[number]=3&page[size]=1",
+            "prev": "/api/v1/entity/shots?page[number]=1&page[size]=1"
+          }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/shots"))
+            .and(query_param("page[number]", "2"))
+            .and(query_param("page[size]", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(page_body, "application/json"))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let link = "/api/v1/entity/shots?page[number]=2&page[size]=1";
+        let resp = session.fetch_page::<Value>(link).await.unwrap();
+        assert_eq!(resp.data.unwrap()[0]["id"], 1002);
+        let links = resp.links.unwrap();
+        assert_eq!(
+            links.next.as_deref(),
+            Some("/api/v1/entity/shots?page[number]=3&page[size]=1")
+        );
+
+        let absolute = format!("{}{}", mock_server.uri(), link);
+        let resp = session.fetch_page::<Value>(&absolute).await.unwrap();
+        assert_eq!(resp.data.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_schema_timeout() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schema"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(r#"{"data": {}}"#, "application/json")
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap()
+            .with_schema_timeout(Duration::from_millis(200));
+
+        let err = session.schema_read::<Value>(None).await.unwrap_err();
+        assert!(matches!(err, Error::Timeout(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page_custom_api_base() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/shotgrid/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/shotgrid/api/v1/entity/shots"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"data": [{ "id": 1002, "type": "Shot" }]}"#,
+                "application/json",
+            ))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None)
+            .unwrap()
+            .with_api_base("/shotgrid/api/v1");
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        // Links are given rooted at the default api base.
+        let resp = session
+            .fetch_page::<Value>("/api/v1/entity/shots?page[number]=2&page[size]=1")
+            .await
+            .unwrap();
+        assert_eq!(resp.data.unwrap()[0]["id"], 1002);
+    }
+
+    #[tokio::test]
+    async fn test_schema_fields_subset() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let schema_body = r##"
+        {
+          "data": {
+            "code": {
+              "data_type": { "value": "text", "editable": false },
+              "name": { "value": "Shot Code", "editable": true }
+            },
+            "sg_status_list": {
+              "data_type": { "value": "status_list", "editable": false },
+              "name": { "value": "Status", "editable": true }
+            },
+            "description": {
+              "data_type": { "value": "text", "editable": false },
+              "name": { "value": "Description", "editable": true }
+            }
+          },
+          "links": { "self": "/api/v1/schema/shots/fields" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schema/Shot/fields"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(schema_body, "application/json"))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let fields = session
+            .schema_fields_subset(None, "Shot", &["code", "sg_status_list"])
+            .await
+            .unwrap();
+        assert_eq!(fields.len(), 2);
+        assert!(fields.contains_key("code"));
+        assert!(fields.contains_key("sg_status_list"));
+
+        match session
+            .schema_fields_subset(None, "Shot", &["code", "sg_nonexistent"])
+            .await
+        {
+            Err(Error::Unexpected(msg)) => assert!(msg.contains("sg_nonexistent"), "{}", msg),
+            other => panic!("Expected Error::Unexpected, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_link_url_to_field() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let update_body = r##"
+        {
+          "data": {
+            "id": 123,
+            "type": "Version",
+            "attributes": {
+              "sg_uploaded_movie": {
+                "url": "https://bucket.example.com/bub_010_v001.mov",
+                "name": "bub_010_v001.mov",
+                "link_type": "web",
+                "type": "Attachment",
+                "id": 789
+              }
+            },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/versions/123" }
+          },
+          "links": { "self": "/api/v1/entity/versions/123" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/entity/Version/123"))
+            .and(BodyContains(r#""link_type":"web""#))
+            .and(BodyContains(
+                r#""url":"https://bucket.example.com/bub_010_v001.mov""#,
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(update_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let attachment = session
+            .link_url_to_field(
+                "Version",
+                123,
+                "sg_uploaded_movie",
+                "https://bucket.example.com/bub_010_v001.mov",
+                Some("bub_010_v001.mov"),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(attachment.id, 789);
+        assert_eq!(attachment.r#type, "Attachment");
+    }
+
+    #[tokio::test]
+    async fn test_entity_overloads() {
+        use wiremock::matchers::query_param;
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let record_body = r##"
+        {
+          "data": {
+            "id": 123,
+            "type": "Shot",
+            "attributes": { "code": "bub_010" },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/shots/123" }
+          },
+          "links": { "self": "/api/v1/entity/shots/123" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Shot/123"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(record_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/entity/Shot/123"))
+            .and(BodyContains(r#""code":"bub_010""#))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(record_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/api/v1/entity/Shot/123"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Shot/123"))
+            .and(query_param("revive", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(record_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let shot = Entity::new("Shot", 123);
+        let read: SingleRecordResponse = session.read_entity(&shot, Some("code")).await.unwrap();
+        assert_eq!(read.data.unwrap().attr_str("code"), Some("bub_010"));
+        let _: Value = session
+            .update_entity(&shot, &json!({"code": "bub_010"}), None)
+            .await
+            .unwrap();
+        session.destroy_entity(&shot).await.unwrap();
+        let _: Value = session.revive_entity(&shot).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_status_values() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let schema_body = r##"
+        {
+          "data": {
+            "data_type": { "value": "status_list", "editable": false },
+            "name": { "value": "Status", "editable": true },
+            "properties": {
+              "default_value": { "value": "wtg", "editable": true },
+              "valid_values": { "value": ["wtg", "ip", "fin"], "editable": true },
+              "display_values": {
+                "value": { "wtg": "Waiting to Start", "ip": "In Progress", "fin": "Final" },
+                "editable": false
+              }
+            }
+          },
+          "links": { "self": "/api/v1/schema/shots/fields/sg_status_list" }
+        }
+        "##;
+
+        let statuses_body = r##"
+        {
+          "data": [
+            {
+              "id": 1,
+              "type": "Status",
+              "attributes": { "code": "ip", "bg_color": "0,156,255" },
+              "relationships": {},
+              "links": { "self": "/api/v1/entity/statuses/1" }
+            },
+            {
+              "id": 2,
+              "type": "Status",
+              "attributes": { "code": "fin", "bg_color": "0,200,0" },
+              "relationships": {},
+              "links": { "self": "/api/v1/entity/statuses/2" }
+            }
+          ],
+          "links": { "self": "/api/v1/entity/statuses/_search" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/schema/Shot/fields/sg_status_list"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(schema_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/Status/_search"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(statuses_body, "application/json"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let values = session
+            .status_values("Shot", "sg_status_list")
+            .await
+            .unwrap();
+
+        let summary: Vec<(&str, Option<&str>, Option<&str>)> = values
+            .iter()
+            .map(|v| (v.code.as_str(), v.name.as_deref(), v.color.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("wtg", Some("Waiting to Start"), None),
+                ("ip", Some("In Progress"), Some("0,156,255")),
+                ("fin", Some("Final"), Some("0,200,0")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_schema_entity_update() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "$$ACCESS_TOKEN$$",
+          "expires_in": 600,
+          "refresh_token": "$$REFRESH_TOKEN$$"
+        }
+        "##;
+
+        let schema_body = r##"
+        {
+          "data": {
+            "name": { "value": "Delivery Shot", "editable": true },
+            "visible": { "value": true, "editable": true }
+          },
+          "links": { "self": "/api/v1/schema/CustomEntity01" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/schema/CustomEntity01"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(schema_body, "application/json"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let resp = session
+            .schema_entity_update("CustomEntity01", "Delivery Shot")
+            .await
+            .unwrap();
+        let name = resp.data.unwrap().name.unwrap().value.unwrap();
+        assert_eq!(name, "Delivery Shot");
+    }
 }
@@ -1,7 +1,8 @@
 use crate::filters::FinalizedFilters;
-use crate::{handle_response, Session};
+use crate::Session;
 use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// Request body of a summarize query.
 #[derive(Serialize, Debug, Clone)]
@@ -55,6 +56,70 @@ pub struct SummaryData {
     pub groups: Option<Vec<SummaryGroups>>,
 }
 
+/// The `(group_name, group_value)` for each level of grouping leading to a
+/// group, outermost first.
+pub type GroupPath<'a> = Vec<(Option<&'a str>, Option<&'a str>)>;
+
+impl SummaryData {
+    /// Flatten the (possibly nested) groups into the innermost groups along
+    /// with the path of groups leading to each.
+    ///
+    /// The path has one entry per [`Grouping`] given to the request, in the
+    /// same order. Groups without summaries are skipped.
+    ///
+    /// ```
+    /// use shotgrid_rs::types::SummarizeResponse;
+    ///
+    /// let resp: SummarizeResponse = serde_json::from_value(serde_json::json!({
+    ///     "data": {
+    ///         "summaries": { "id": 3 },
+    ///         "groups": [{
+    ///             "group_name": "Character",
+    ///             "group_value": "Character",
+    ///             "summaries": { "id": 3 },
+    ///             "groups": [
+    ///                 { "group_name": "ip", "group_value": "ip", "summaries": { "id": 2 } },
+    ///                 { "group_name": "apr", "group_value": "apr", "summaries": { "id": 1 } },
+    ///             ]
+    ///         }]
+    ///     }
+    /// })).unwrap();
+    ///
+    /// for (path, summaries) in resp.data.leaves() {
+    ///     println!("{:?}: {}", path, summaries["id"]);
+    /// }
+    /// ```
+    pub fn leaves(&self) -> impl Iterator<Item = (GroupPath<'_>, &SummaryMap)> {
+        let mut leaves = vec![];
+        if let Some(groups) = &self.groups {
+            for group in groups {
+                collect_leaves(group, vec![], &mut leaves);
+            }
+        }
+        leaves.into_iter()
+    }
+}
+
+fn collect_leaves<'a>(
+    group: &'a SummaryGroups,
+    mut path: GroupPath<'a>,
+    leaves: &mut Vec<(GroupPath<'a>, &'a SummaryMap)>,
+) {
+    path.push((group.group_name.as_deref(), group.group_value.as_deref()));
+    match &group.groups {
+        Some(groups) if !groups.is_empty() => {
+            for child in groups {
+                collect_leaves(child, path.clone(), leaves);
+            }
+        }
+        _ => {
+            if let Some(summaries) = &group.summaries {
+                leaves.push((path, summaries));
+            }
+        }
+    }
+}
+
 /// <https://developer.shotgridsoftware.com/rest-api/#tocSsummarizeresponse>
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SummarizeResponse {
@@ -307,6 +372,8 @@ pub struct SummarizeReqBuilder<'a> {
     // TODO: move these to a builder
     grouping: Option<Vec<Grouping>>,
     options: Option<SummaryOptions>,
+    truncate_groups: Option<usize>,
+    timeout: Option<Duration>,
 }
 
 impl<'a> SummarizeReqBuilder<'a> {
@@ -323,6 +390,8 @@ impl<'a> SummarizeReqBuilder<'a> {
             summary_fields,
             grouping: None,
             options: None,
+            truncate_groups: None,
+            timeout: None,
         }
     }
 
@@ -338,6 +407,26 @@ impl<'a> SummarizeReqBuilder<'a> {
         self
     }
 
+    /// Drop all but the first `value` groups at each level of grouping once
+    /// the response has been read.
+    ///
+    /// This is *not* a server-side limit; ShotGrid has no summary option for
+    /// one. The server still computes and sends every group, so the request
+    /// costs just as much. The summaries of the parent groups (and the
+    /// overall summaries) still count the records of the groups dropped.
+    pub fn truncate_groups(mut self, value: usize) -> Self {
+        self.truncate_groups = Some(value);
+        self
+    }
+
+    /// Give the summary `value` to complete, rather than the HTTP client's
+    /// timeout. Large groupings can take a while to compute. See
+    /// [`Client`](`crate::Client`#timeouts).
+    pub fn timeout(mut self, value: Duration) -> Self {
+        self.timeout = Some(value);
+        self
+    }
+
     pub async fn execute(self) -> crate::Result<SummarizeResponse> {
         // FIXME: python api treats filters as required (and we fallback to empty array).
         //  Maybe just make it required?
@@ -356,20 +445,117 @@ impl<'a> SummarizeReqBuilder<'a> {
 
         let (sg, token) = self.session.get_sg().await?;
 
-        let req = sg
+        let mut req = sg
             .http
             .post(&format!(
-                "{}/api/v1/entity/{}/_summarize",
-                sg.sg_server, self.entity
+                "{}{}/entity/{}/_summarize",
+                sg.sg_server, sg.api_base, self.entity
             ))
             .header("Accept", "application/json")
-            .bearer_auth(token)
-            .header("Content-Type", content_type)
-            // The content type is being set to ShotGrid's custom mime types
-            // to indicate the shape of the filter payload. Do not be tempted to
-            // use `.json()` here instead of `.body()` or you'll end up
-            // reverting the header set above.
-            .body(json!(body).to_string());
-        handle_response(req.send().await?).await
+            .bearer_auth(token);
+        if let Some(timeout) = self.timeout {
+            req = req.timeout(timeout);
+        }
+        let mut resp: SummarizeResponse = sg
+            .send_with_filter_mime(req, content_type, json!(body).to_string())
+            .await?;
+        if let Some(max) = self.truncate_groups {
+            drop_extra_groups(&mut resp.data.groups, max);
+        }
+        Ok(resp)
+    }
+}
+
+/// Drop all but the first `max` groups, at every level of nesting.
+fn drop_extra_groups(groups: &mut Option<Vec<SummaryGroups>>, max: usize) {
+    if let Some(groups) = groups {
+        groups.truncate(max);
+        for group in groups.iter_mut() {
+            drop_extra_groups(&mut group.groups, max);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_data_leaves() {
+        let data: SummaryData = serde_json::from_value(json!({
+            "summaries": { "id": 4 },
+            "groups": [
+                {
+                    "group_name": "Character",
+                    "group_value": "Character",
+                    "summaries": { "id": 3 },
+                    "groups": [
+                        { "group_name": "ip", "group_value": "ip", "summaries": { "id": 2 } },
+                        { "group_name": "apr", "group_value": "apr", "summaries": { "id": 1 } }
+                    ]
+                },
+                {
+                    "group_name": "Prop",
+                    "group_value": "Prop",
+                    "summaries": { "id": 1 },
+                    "groups": [
+                        { "group_name": "wtg", "group_value": "wtg", "summaries": { "id": 1 } }
+                    ]
+                }
+            ]
+        }))
+        .unwrap();
+
+        let leaves: Vec<_> = data
+            .leaves()
+            .map(|(path, summaries)| {
+                let path: Vec<_> = path.into_iter().map(|(name, _)| name.unwrap()).collect();
+                (path, summaries["id"].as_i64().unwrap())
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                (vec!["Character", "ip"], 2),
+                (vec!["Character", "apr"], 1),
+                (vec!["Prop", "wtg"], 1),
+            ],
+            leaves
+        );
+    }
+
+    #[test]
+    fn test_drop_extra_groups() {
+        let mut data: SummaryData = serde_json::from_value(json!({
+            "groups": [
+                {
+                    "group_name": "Character",
+                    "groups": [
+                        { "group_name": "ip", "summaries": { "id": 2 } },
+                        { "group_name": "apr", "summaries": { "id": 1 } }
+                    ]
+                },
+                { "group_name": "Prop", "summaries": { "id": 1 } }
+            ]
+        }))
+        .unwrap();
+
+        drop_extra_groups(&mut data.groups, 1);
+        let leaves: Vec<_> = data
+            .leaves()
+            .map(|(path, _)| {
+                path.into_iter()
+                    .map(|(name, _)| name.unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(vec![vec!["Character", "ip"]], leaves);
+    }
+
+    #[test]
+    fn test_summary_data_leaves_ungrouped() {
+        let data: SummaryData =
+            serde_json::from_value(json!({ "summaries": { "id": 4 } })).unwrap();
+        assert_eq!(0, data.leaves().count());
     }
 }
@@ -1,9 +1,10 @@
 use crate::filters::FinalizedFilters;
-use crate::types::PaginationParameter;
-use crate::{handle_response, Error, Session};
+use crate::types::{PaginatedRecordResponse, PaginationParameter, Record};
+use crate::{Error, Session};
 use serde::de::DeserializeOwned;
-use serde_json::json;
+use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::time::Duration;
 
 pub type EntityFilters<'a> = HashMap<&'a str, FinalizedFilters>;
 
@@ -18,7 +19,9 @@ fn get_entity_filters_mime(entity_filters: &EntityFilters) -> crate::Result<&'st
         let first = filters.next().unwrap().get_mime();
         for filter in filters {
             if first != filter.get_mime() {
-                return Err(Error::InvalidFilters);
+                return Err(Error::InvalidFilters(String::from(
+                    "all entity filters must be basic, or all must be complex",
+                )));
             }
         }
         Ok(first)
@@ -34,6 +37,7 @@ pub struct TextSearchBuilder<'a> {
     text: Option<&'a str>,
     sort: Option<String>,
     pagination: Option<PaginationParameter>,
+    timeout: Option<Duration>,
 }
 
 impl<'a> TextSearchBuilder<'a> {
@@ -48,6 +52,7 @@ impl<'a> TextSearchBuilder<'a> {
             text,
             sort: None,
             pagination: None,
+            timeout: None,
         }
     }
 
@@ -78,10 +83,7 @@ impl<'a> TextSearchBuilder<'a> {
         self
     }
 
-    pub async fn execute<D: 'static>(self) -> crate::Result<D>
-    where
-        D: DeserializeOwned,
-    {
+    fn body(&self) -> HashMap<&'static str, Value> {
         let mut body = HashMap::new();
 
         body.insert("entity_types", json!(self.entity_filters));
@@ -89,34 +91,81 @@ impl<'a> TextSearchBuilder<'a> {
         if let Some(text) = self.text {
             body.insert("text", json!(text));
         }
-        if let Some(pagination) = self.pagination {
+        if let Some(pagination) = &self.pagination {
             body.insert("page", json!(pagination));
         }
 
-        if let Some(sort) = self.sort {
+        if let Some(sort) = &self.sort {
             body.insert("sort", json!(sort));
         }
 
-        let content_type = get_entity_filters_mime(&self.entity_filters)?;
-
         body.insert("entity_filters", json!(self.entity_filters));
+        body
+    }
+
+    /// Give the text search `value` to complete, rather than the HTTP
+    /// client's timeout. See [`Client`](`crate::Client`#timeouts).
+    pub fn timeout(mut self, value: Duration) -> Self {
+        self.timeout = Some(value);
+        self
+    }
+
+    pub async fn execute<D: 'static>(self) -> crate::Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        if self.session.is_api_user() {
+            return Err(Error::BadClientConfig(String::from(
+                "text_search requires a HumanUser; use authenticate_script_as_user",
+            )));
+        }
+
+        let content_type = get_entity_filters_mime(&self.entity_filters)?;
+        let body = self.body();
 
         let (sg, token) = self.session.get_sg().await?;
-        let req = sg
+        let mut req = sg
             .http
-            .post(&format!("{}/api/v1/entity/_text_search", sg.sg_server))
-            .header("Content-Type", content_type)
+            .post(&format!(
+                "{}{}/entity/_text_search",
+                sg.sg_server, sg.api_base
+            ))
             .header("Accept", "application/json")
-            .bearer_auth(&token)
-            .body(json!(body).to_string());
-        handle_response(req.send().await?).await
+            .bearer_auth(&token);
+        if let Some(timeout) = self.timeout {
+            req = req.timeout(timeout);
+        }
+        sg.send_with_filter_mime(req, content_type, json!(body).to_string())
+            .await
+    }
+
+    /// Run the search, sorting the matching records into groups by their
+    /// entity type, such as `"Shot"` or `"Note"`.
+    ///
+    /// Records are kept in the order given by ShotGrid within each group.
+    pub async fn execute_grouped(self) -> crate::Result<HashMap<String, Vec<Record>>> {
+        let resp: PaginatedRecordResponse = self.execute().await?;
+        Ok(group_by_type(resp.data.unwrap_or_default()))
+    }
+}
+
+/// Partition records by their `type`, leaving out any without one.
+fn group_by_type(records: Vec<Record>) -> HashMap<String, Vec<Record>> {
+    let mut groups: HashMap<String, Vec<Record>> = HashMap::new();
+    for record in records {
+        if let Some(entity_type) = record.r#type.clone() {
+            groups.entry(entity_type).or_default().push(record);
+        }
     }
+    groups
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::filters::{self, field};
+    use crate::session::Login;
+    use crate::{Client, TokenResponse};
 
     #[test]
     fn test_get_entity_filters_mime_array_entity_types() {
@@ -176,14 +225,54 @@ mod tests {
 
         let result = get_entity_filters_mime(&filters);
         match result {
-            Err(Error::InvalidFilters) => assert!(true),
+            Err(Error::InvalidFilters(_)) => assert!(true),
             _ => assert!(false, "Expected Error::InvalidFilters"),
         }
     }
 
+    #[tokio::test]
+    async fn test_execute_as_api_user_fails() {
+        let sg = Client::new(String::from("https://shotgrid.example.com"), None, None).unwrap();
+        let session = Session::new(
+            &sg,
+            TokenResponse {
+                token_type: String::from("Bearer"),
+                access_token: String::from("xxxx"),
+                expires_in: 600,
+                refresh_token: String::from("xxxx"),
+            },
+        )
+        .with_login(Login::ApiUser(String::from("my-api-user")));
+
+        let result = session
+            .text_search(Some("Norman"), EntityFilters::new())
+            .execute::<Value>()
+            .await;
+        assert!(matches!(result, Err(Error::BadClientConfig(_))));
+    }
+
     #[test]
     fn test_get_entity_filters_mime_empty_filters_ok() {
         let filters = vec![].into_iter().collect();
         assert!(get_entity_filters_mime(&filters).is_ok());
     }
+
+    #[test]
+    fn test_group_by_type() {
+        let resp: PaginatedRecordResponse = serde_json::from_value(json!({
+            "data": [
+                { "id": 1, "type": "Shot" },
+                { "id": 2, "type": "Note" },
+                { "id": 3, "type": "Shot" },
+                { "id": 4 }
+            ]
+        }))
+        .unwrap();
+
+        let groups = group_by_type(resp.data.unwrap());
+        assert_eq!(groups.len(), 2);
+        let shot_ids: Vec<_> = groups["Shot"].iter().map(|r| r.id).collect();
+        assert_eq!(shot_ids, vec![Some(1), Some(3)]);
+        assert_eq!(groups["Note"].len(), 1);
+    }
 }
@@ -1,12 +1,14 @@
 pub use crate::schema::{
-    CreateFieldRequest, CreateUpdateFieldProperty, FieldDataType, SchemaEntitiesResponse,
-    SchemaEntityRecord, SchemaEntityResponse, SchemaFieldProperties, SchemaFieldRecord,
-    SchemaFieldResponse, SchemaFieldsResponse, SchemaResponseValue, UpdateFieldRequest,
+    CreateFieldRequest, CreateUpdateFieldProperty, FieldDataType, FieldInfo,
+    SchemaEntitiesResponse, SchemaEntityRecord, SchemaEntityResponse, SchemaFieldProperties,
+    SchemaFieldRecord, SchemaFieldResponse, SchemaFieldsResponse, SchemaResponseValue, StatusValue,
+    UpdateEntityRequest, UpdateFieldRequest,
 };
 pub use crate::summarize::{
-    Grouping, GroupingDirection, GroupingType, SummarizeRequest, SummarizeResponse, SummaryData,
-    SummaryField, SummaryFieldType, SummaryMap, SummaryOptions,
+    GroupPath, Grouping, GroupingDirection, GroupingType, SummarizeRequest, SummarizeResponse,
+    SummaryData, SummaryField, SummaryFieldType, SummaryGroups, SummaryMap, SummaryOptions,
 };
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 use std::collections::HashMap;
 
@@ -21,6 +23,68 @@ pub struct ActivityUpdate {
     created_by: Option<serde_json::Map<String, Value>>,
 }
 
+impl ActivityUpdate {
+    /// The id of the update itself.
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    /// What kind of change the update is for.
+    ///
+    /// Gives `None` when the update type is missing, or isn't one this crate
+    /// knows about. See [`ActivityUpdate::update_type_str()`] for the raw
+    /// value.
+    pub fn update_type(&self) -> Option<ActivityUpdateType> {
+        let update_type = self.update_type.clone()?;
+        serde_json::from_value(Value::String(update_type)).ok()
+    }
+
+    /// The update type as given by ShotGrid, such as `"create_reply"`.
+    pub fn update_type_str(&self) -> Option<&str> {
+        self.update_type.as_deref()
+    }
+
+    /// Details of the update, which vary by update type. For example,
+    /// updates to a field give the `attribute_name`, `old_value` and
+    /// `new_value`.
+    pub fn meta(&self) -> Option<&serde_json::Map<String, Value>> {
+        self.meta.as_ref()
+    }
+
+    /// Whether the current user has read the update, treating a missing
+    /// `read` as `false`.
+    pub fn is_read(&self) -> bool {
+        self.read.unwrap_or(false)
+    }
+
+    /// The entity the update is about.
+    pub fn primary_entity(&self) -> Option<Entity> {
+        map_to_entity(self.primary_entity.as_ref()?)
+    }
+
+    /// The user (or API user) who made the update.
+    pub fn created_by(&self) -> Option<Entity> {
+        map_to_entity(self.created_by.as_ref()?)
+    }
+}
+
+/// Read the `type` and `id` of an entity given as a JSON object, ignoring
+/// the rest of its keys.
+fn map_to_entity(map: &serde_json::Map<String, Value>) -> Option<Entity> {
+    serde_json::from_value(Value::Object(map.clone())).ok()
+}
+
+/// The kinds of change recorded in an entity's activity stream.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityUpdateType {
+    Create,
+    Update,
+    Delete,
+    Revive,
+    CreateReply,
+}
+
 /// Alternate images
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum AltImages {
@@ -30,6 +94,18 @@ pub enum AltImages {
     Thumbnail,
 }
 
+/// How an update treats the existing value of a multi-entity field.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MultiEntityMode {
+    /// Replace the field's value with the given entities.
+    Set,
+    /// Add the given entities to those already in the field.
+    Add,
+    /// Remove the given entities from the field, leaving the rest.
+    Remove,
+}
+
 /// <https://developer.shotgridsoftware.com/rest-api/?shell#tocSbatchcreateoptionsparameter>
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct BatchCreateOptionsParameter {
@@ -78,6 +154,13 @@ impl Entity {
     }
 }
 
+/// Renders as `Type:id`, such as `Asset:123`.
+impl std::fmt::Display for Entity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.r#type, self.id)
+    }
+}
+
 /// EntityActivityStreamData is not represented as a named schema in the ShotGrid OpenAPI Spec.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EntityActivityStreamData {
@@ -107,7 +190,7 @@ pub struct EntityThreadContentsData {
 }
 
 /// <https://developer.shotgridsoftware.com/rest-api/#tocSentitythreadcontentsresponse>
-pub type EntityThreadContentsResponse = SingleResourceResponse<EntityThreadContentsData, SelfLink>;
+pub type EntityThreadContentsResponse = ResourceArrayResponse<EntityThreadContentsData, SelfLink>;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ErrorResponse {
@@ -177,6 +260,23 @@ pub struct HierarchyExpandRequest {
     pub seed_entity_field: Option<String>,
 }
 
+impl HierarchySearchRequest {
+    /// A search with no `root_path` or `seed_entity_field`.
+    ///
+    /// ```
+    /// use shotgrid_rs::types::{HierarchySearchCriteria, HierarchySearchRequest};
+    ///
+    /// let data = HierarchySearchRequest::new(HierarchySearchCriteria::by_entity("Asset", 123));
+    /// ```
+    pub fn new(search_criteria: HierarchySearchCriteria) -> Self {
+        Self {
+            root_path: None,
+            search_criteria,
+            seed_entity_field: None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct HierarchyExpandResponseDataRefValue {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -260,6 +360,18 @@ pub enum HierarchySearchCriteria {
     Entity(Entity),
 }
 
+impl HierarchySearchCriteria {
+    /// Search by name.
+    pub fn by_string<S: Into<String>>(value: S) -> Self {
+        HierarchySearchCriteria::SearchString(value.into())
+    }
+
+    /// Search for a specific entity.
+    pub fn by_entity<S: Into<String>>(r#type: S, id: i32) -> Self {
+        HierarchySearchCriteria::Entity(Entity::new(r#type, id))
+    }
+}
+
 /// <https://developer.shotgridsoftware.com/rest-api/#tocShierarchysearchrequest>
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct HierarchySearchRequest {
@@ -340,6 +452,31 @@ impl Default for PaginationParameter {
     }
 }
 
+/// The most records ShotGrid will give in a single page.
+pub const MAX_PAGE_SIZE: usize = 500;
+
+impl PaginationParameter {
+    /// Check the page number and size are ones ShotGrid will accept, giving
+    /// an [`Error::InvalidPagination`](`crate::Error::InvalidPagination`) if
+    /// not.
+    pub(crate) fn validate(&self) -> crate::Result<()> {
+        if self.number == Some(0) {
+            return Err(crate::Error::InvalidPagination(String::from(
+                "Page numbers start at 1, not 0.",
+            )));
+        }
+        match self.size {
+            Some(size) if size == 0 || size > MAX_PAGE_SIZE => {
+                Err(crate::Error::InvalidPagination(format!(
+                    "Page size must be between 1 and {}, got {}.",
+                    MAX_PAGE_SIZE, size
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 /// <https://developer.shotgridsoftware.com/rest-api/#tocSpaginationlinks>
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PaginationLinks {
@@ -386,6 +523,111 @@ pub struct Record {
     pub links: Option<SelfLink>,
 }
 
+impl Record {
+    /// The url for the record's thumbnail, read from the `image` field.
+    ///
+    /// Gives `None` when the record has no thumbnail, or when `image` was not
+    /// included in the return fields for the request.
+    pub fn thumbnail_url(&self) -> Option<&str> {
+        self.image_url("image")
+    }
+
+    /// The url for the record's filmstrip thumbnail, read from the
+    /// `filmstrip_image` field.
+    ///
+    /// Gives `None` when the record has no filmstrip, or when `filmstrip_image`
+    /// was not included in the return fields for the request.
+    pub fn filmstrip_url(&self) -> Option<&str> {
+        self.image_url("filmstrip_image")
+    }
+
+    /// Read a string field from the record's attributes.
+    ///
+    /// Gives `None` when the field is missing, null, or not a string.
+    pub fn attr_str(&self, field: &str) -> Option<&str> {
+        self.attr(field).and_then(Value::as_str)
+    }
+
+    /// Read an integer field from the record's attributes.
+    ///
+    /// Gives `None` when the field is missing, null, or not an integer.
+    pub fn attr_i64(&self, field: &str) -> Option<i64> {
+        self.attr(field).and_then(Value::as_i64)
+    }
+
+    /// Read a checkbox field from the record's attributes.
+    ///
+    /// Gives `None` when the field is missing, null, or not a bool.
+    pub fn attr_bool(&self, field: &str) -> Option<bool> {
+        self.attr(field).and_then(Value::as_bool)
+    }
+
+    /// Read a single-entity link from the record's relationships.
+    ///
+    /// Relationships are given in the form `{"data": {"type": ..., "id": ...}}`.
+    /// Gives `None` when the relationship is missing, empty, or is a
+    /// multi-entity link.
+    pub fn related_entity(&self, field: &str) -> Option<Entity> {
+        let data = self.relationships.as_ref()?.get(field)?.get("data")?;
+        serde_json::from_value(data.clone()).ok()
+    }
+
+    /// Read the entities linked by a relationship, whether a single or
+    /// multi-entity link.
+    ///
+    /// Multi-entity links are given in the form `{"data": [{...}, ...]}`, and
+    /// single-entity links as described in [`Record::related_entity()`].
+    /// Gives an empty `Vec` when the relationship is missing or empty.
+    pub fn related_entities(&self, field: &str) -> Vec<Entity> {
+        let data = self
+            .relationships
+            .as_ref()
+            .and_then(|rels| rels.get(field))
+            .and_then(|rel| rel.get("data"));
+        match data {
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(|item| serde_json::from_value(item.clone()).ok())
+                .collect(),
+            Some(item) => serde_json::from_value(item.clone())
+                .ok()
+                .into_iter()
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Read a text field holding a JSON document, parsing it into `T`.
+    ///
+    /// Gives an [`Error::Unexpected`](`crate::Error::Unexpected`) when the
+    /// field is missing or not a string, and an
+    /// [`Error::JsonParse`](`crate::Error::JsonParse`) when the string is not
+    /// valid JSON for `T`.
+    pub fn attr_json<T>(&self, field: &str) -> crate::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let text = self.attr_str(field).ok_or_else(|| {
+            crate::Error::Unexpected(format!("Field `{}` is missing or not a string.", field))
+        })?;
+        Ok(serde_json::from_str(text)?)
+    }
+
+    fn attr(&self, field: &str) -> Option<&Value> {
+        self.attributes.as_ref()?.get(field)
+    }
+
+    /// Image fields are usually a plain url string, but can also be given as
+    /// an object with the url nested under a `url` key.
+    fn image_url(&self, field: &str) -> Option<&str> {
+        match self.attributes.as_ref()?.get(field)? {
+            Value::String(url) => Some(url),
+            Value::Object(obj) => obj.get("url").and_then(Value::as_str),
+            _ => None,
+        }
+    }
+}
+
 /// <https://developer.shotgridsoftware.com/rest-api/#tocSrefreshrequest>
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RefreshRequest {
@@ -399,6 +641,8 @@ pub struct RefreshRequest {
 /// The value is either a Record or a vec of records
 pub type RelationshipsResponse = SingleResourceResponse<Value, SelfLink>;
 
+/// Limits a query to active records, or to retired (deleted, but revivable)
+/// records.
 #[derive(Clone, Debug, Serialize)]
 pub enum ReturnOnly {
     Active,
@@ -419,6 +663,32 @@ pub struct SelfLink {
     pub self_link: Option<String>,
 }
 
+/// Version information about the ShotGrid server, as returned by
+/// [`Client::server_info()`](`crate::Client::server_info()`).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ServerInfo {
+    /// The server version as `[major, minor, patch]`.
+    pub version: Option<Vec<u32>>,
+    pub shotgun_version: Option<String>,
+    pub api_version: Option<String>,
+    pub s3_uploads_enabled: Option<bool>,
+}
+
+impl ServerInfo {
+    /// Check if the server version is the same or newer than `min`, for
+    /// example `info.version_at_least(&[8, 16])`.
+    ///
+    /// Returns `false` when the server did not report a version.
+    pub fn version_at_least(&self, min: &[u32]) -> bool {
+        self.version
+            .as_ref()
+            .map(|version| version.as_slice() >= min)
+            .unwrap_or(false)
+    }
+}
+
+pub type ServerInfoResponse = SingleResourceResponse<ServerInfo, SelfLink>;
+
 /// <https://developer.shotgridsoftware.com/rest-api/#tocSsinglerecordresponse>
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SingleRecordResponse {
@@ -426,6 +696,26 @@ pub struct SingleRecordResponse {
     pub links: Option<SelfLink>,
 }
 
+impl SingleRecordResponse {
+    /// Read a single-entity link from the record in the response.
+    ///
+    /// See [`Record::related_entity()`].
+    pub fn related_entity(&self, field: &str) -> Option<Entity> {
+        self.data.as_ref()?.related_entity(field)
+    }
+
+    /// Read the entities linked by a relationship of the record in the
+    /// response.
+    ///
+    /// See [`Record::related_entities()`].
+    pub fn related_entities(&self, field: &str) -> Vec<Entity> {
+        self.data
+            .as_ref()
+            .map(|record| record.related_entities(field))
+            .unwrap_or_default()
+    }
+}
+
 /// Unlike SingleRecordResponse, this is not part of ShotGrid's REST API.
 /// This is a generic.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -436,6 +726,64 @@ pub struct SingleResourceResponse<R, L> {
     pub links: Option<L>,
 }
 
+/// The commonly used site preferences, as returned by
+/// [`Session::site_preferences()`](`crate::Session::site_preferences()`).
+///
+/// Preferences missing from the response (or of an unexpected type) are left
+/// as `None`. Everything ShotGrid sent is kept in `raw` for the rest.
+#[derive(Clone, Debug, Default)]
+pub struct SitePreferences {
+    /// How dates are displayed, ie. `"Relative"` or `"Absolute"`.
+    pub format_date_fields: Option<String>,
+    /// The order of day and month in dates, ie. `"month_day"`.
+    pub date_component_order: Option<String>,
+    /// Whether times use a 12 or 24 hour clock.
+    pub format_time_hour_fields: Option<String>,
+    pub format_currency_fields_display_dollar_sign: Option<bool>,
+    pub format_currency_fields_decimal_options: Option<String>,
+    pub format_currency_fields_negative_options: Option<String>,
+    pub format_number_fields: Option<String>,
+    pub format_float_fields: Option<String>,
+    pub format_float_fields_rounding: Option<String>,
+    pub format_footage_fields: Option<String>,
+    /// The length of a working day, used when scheduling.
+    pub hours_per_day: Option<f64>,
+    /// The last day of the working week, ie. `"friday"`.
+    pub last_day_work_week: Option<String>,
+    pub support_local_storage: Option<bool>,
+    /// The preferences exactly as ShotGrid sent them.
+    pub raw: Value,
+}
+
+impl From<Value> for SitePreferences {
+    fn from(raw: Value) -> Self {
+        let string = |key: &str| raw[key].as_str().map(String::from);
+        let boolean = |key: &str| raw[key].as_bool();
+        SitePreferences {
+            format_date_fields: string("format_date_fields"),
+            date_component_order: string("date_component_order"),
+            format_time_hour_fields: string("format_time_hour_fields"),
+            format_currency_fields_display_dollar_sign: boolean(
+                "format_currency_fields_display_dollar_sign",
+            ),
+            format_currency_fields_decimal_options: string(
+                "format_currency_fields_decimal_options",
+            ),
+            format_currency_fields_negative_options: string(
+                "format_currency_fields_negative_options",
+            ),
+            format_number_fields: string("format_number_fields"),
+            format_float_fields: string("format_float_fields"),
+            format_float_fields_rounding: string("format_float_fields_rounding"),
+            format_footage_fields: string("format_footage_fields"),
+            hours_per_day: raw["hours_per_day"].as_f64(),
+            last_day_work_week: string("last_day_work_week"),
+            support_local_storage: boolean("support_local_storage"),
+            raw,
+        }
+    }
+}
+
 /// <https://developer.shotgridsoftware.com/rest-api/#tocStextsearchrequest>
 #[derive(Serialize, Debug, Clone)]
 pub struct TextSearchRequest {
@@ -531,3 +879,223 @@ pub struct WorkDayRulesData {
     pub description: Option<String>,
     pub reason: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_update_accessors() {
+        let update: ActivityUpdate = serde_json::from_value(serde_json::json!({
+            "id": 42,
+            "update_type": "update",
+            "meta": {
+                "attribute_name": "sg_status_list",
+                "old_value": "ip",
+                "new_value": "fin"
+            },
+            "read": null,
+            "primary_entity": { "type": "Shot", "id": 123, "name": "bub_010" },
+            "created_by": { "type": "HumanUser", "id": 7, "name": "Nick" }
+        }))
+        .unwrap();
+
+        assert_eq!(update.id(), Some(42));
+        assert_eq!(update.update_type(), Some(ActivityUpdateType::Update));
+        assert_eq!(update.meta().unwrap()["new_value"], "fin");
+        assert!(!update.is_read());
+        assert_eq!(update.primary_entity().unwrap().to_string(), "Shot:123");
+        assert_eq!(update.created_by().unwrap().to_string(), "HumanUser:7");
+
+        let reply: ActivityUpdate = serde_json::from_value(serde_json::json!({
+            "update_type": "create_reply",
+            "created_by": null
+        }))
+        .unwrap();
+        assert_eq!(reply.update_type(), Some(ActivityUpdateType::CreateReply));
+        assert!(reply.created_by().is_none());
+
+        let unknown: ActivityUpdate =
+            serde_json::from_value(serde_json::json!({ "update_type": "something_new" })).unwrap();
+        assert_eq!(unknown.update_type(), None);
+        assert_eq!(unknown.update_type_str(), Some("something_new"));
+    }
+
+    #[test]
+    fn test_site_preferences_from_value() {
+        let prefs = SitePreferences::from(serde_json::json!({
+            "format_date_fields": "Relative",
+            "date_component_order": "month_day",
+            "format_time_hour_fields": "12 hour",
+            "format_currency_fields_display_dollar_sign": false,
+            "hours_per_day": 8.0,
+            "last_day_work_week": null,
+            "view_master_settings": "{}"
+        }));
+
+        assert_eq!(prefs.format_date_fields.as_deref(), Some("Relative"));
+        assert_eq!(prefs.date_component_order.as_deref(), Some("month_day"));
+        assert_eq!(
+            prefs.format_currency_fields_display_dollar_sign,
+            Some(false)
+        );
+        assert_eq!(prefs.hours_per_day, Some(8.0));
+        assert_eq!(prefs.last_day_work_week, None);
+        assert_eq!(prefs.format_footage_fields, None);
+        assert_eq!(prefs.raw["view_master_settings"], "{}");
+    }
+
+    #[test]
+    fn test_server_info_version_at_least() {
+        let info: ServerInfo = serde_json::from_value(serde_json::json!({
+            "shotgun_version": "v8.16.0.0-a1b2c3",
+            "api_version": "v1.x",
+            "version": [8, 16, 0]
+        }))
+        .unwrap();
+
+        assert!(info.version_at_least(&[8, 16]));
+        assert!(info.version_at_least(&[8, 15, 3]));
+        assert!(!info.version_at_least(&[8, 16, 1]));
+        assert!(!info.version_at_least(&[9]));
+    }
+
+    #[test]
+    fn test_record_thumbnail_url() {
+        let record: Record = serde_json::from_value(serde_json::json!({
+            "id": 123,
+            "type": "Asset",
+            "attributes": {
+                "image": "https://sg-media.example.com/thumbnail.jpg",
+                "filmstrip_image": {
+                    "url": "https://sg-media.example.com/filmstrip.jpg"
+                }
+            },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/assets/123" }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            Some("https://sg-media.example.com/thumbnail.jpg"),
+            record.thumbnail_url()
+        );
+        assert_eq!(
+            Some("https://sg-media.example.com/filmstrip.jpg"),
+            record.filmstrip_url()
+        );
+    }
+
+    #[test]
+    fn test_record_attr_json() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Metadata {
+            frame_range: Vec<i32>,
+            colorspace: String,
+        }
+
+        let record: Record = serde_json::from_value(serde_json::json!({
+            "id": 123,
+            "type": "Version",
+            "attributes": {
+                "sg_metadata": r#"{"frame_range": [1001, 1100], "colorspace": "ACEScg"}"#,
+                "description": "not json",
+            },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/versions/123" }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            Metadata {
+                frame_range: vec![1001, 1100],
+                colorspace: String::from("ACEScg"),
+            },
+            record.attr_json("sg_metadata").unwrap()
+        );
+
+        match record.attr_json::<Metadata>("description") {
+            Err(crate::Error::JsonParse(_)) => {}
+            other => panic!("Expected Error::JsonParse, got {:?}", other),
+        }
+
+        match record.attr_json::<Metadata>("sg_missing") {
+            Err(crate::Error::Unexpected(_)) => {}
+            other => panic!("Expected Error::Unexpected, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_record_typed_accessors() {
+        let record: Record = serde_json::from_value(serde_json::json!({
+            "id": 123,
+            "type": "Version",
+            "attributes": {
+                "code": "bub_010_v001",
+                "sg_first_frame": 1001,
+                "sg_uploaded_movie_transcoding_status": true,
+                "description": null,
+            },
+            "relationships": {
+                "entity": {
+                    "data": { "type": "Shot", "id": 456, "name": "bub_010" },
+                    "links": { "related": "/api/v1/entity/shots/456" }
+                },
+                "sg_task": { "data": null },
+                "playlists": {
+                    "data": [{ "type": "Playlist", "id": 789, "name": "dailies" }]
+                }
+            },
+            "links": { "self": "/api/v1/entity/versions/123" }
+        }))
+        .unwrap();
+
+        assert_eq!(Some("bub_010_v001"), record.attr_str("code"));
+        assert_eq!(Some(1001), record.attr_i64("sg_first_frame"));
+        assert_eq!(
+            Some(true),
+            record.attr_bool("sg_uploaded_movie_transcoding_status")
+        );
+        assert_eq!(None, record.attr_str("description"));
+        assert_eq!(None, record.attr_i64("code"));
+        assert_eq!(None, record.attr_bool("sg_missing"));
+
+        let entity = record.related_entity("entity").unwrap();
+        assert_eq!(("Shot", 456), (entity.r#type.as_str(), entity.id));
+        assert!(record.related_entity("sg_task").is_none());
+        assert!(record.related_entity("playlists").is_none());
+        assert!(record.related_entity("sg_missing").is_none());
+
+        let playlists = record.related_entities("playlists");
+        assert_eq!(1, playlists.len());
+        assert_eq!(
+            ("Playlist", 789),
+            (playlists[0].r#type.as_str(), playlists[0].id)
+        );
+        assert_eq!(1, record.related_entities("entity").len());
+        assert!(record.related_entities("sg_task").is_empty());
+        assert!(record.related_entities("sg_missing").is_empty());
+
+        let resp = SingleRecordResponse {
+            data: Some(record),
+            links: None,
+        };
+        assert_eq!(456, resp.related_entity("entity").unwrap().id);
+        assert_eq!(789, resp.related_entities("playlists")[0].id);
+    }
+
+    #[test]
+    fn test_record_thumbnail_url_null() {
+        let record: Record = serde_json::from_value(serde_json::json!({
+            "id": 123,
+            "type": "Asset",
+            "attributes": { "image": null },
+            "relationships": {},
+            "links": { "self": "/api/v1/entity/assets/123" }
+        }))
+        .unwrap();
+
+        assert_eq!(None, record.thumbnail_url());
+        assert_eq!(None, record.filmstrip_url());
+    }
+}
@@ -11,9 +11,10 @@
 //! Uploads that target an entity without specifying a field are thought to be
 //! linked to *the record* as opposed to *a field*.
 //!
-//! Uploads that target the `images` field specifically are thought to be
+//! Uploads that target the `image` field specifically are thought to be
 //! "thumbnail uploads" which are handled slightly differently, ignoring any
-//! display name or tag data sent with the request.
+//! display name or tag data sent with the request. Use
+//! [`Session::upload_thumbnail()`] to make this explicit.
 //!
 //! Uploads that target *other fields* or a *record* are thought to be
 //! "attachment uploads" and will accept a display name and tags.
@@ -30,22 +31,65 @@
 //! For more on this, refer to the ShotGrid REST API docs:
 //!
 //! <https://developer.shotgridsoftware.com/rest-api/#shotgrid-rest-api-Uploading-and-Downloading-Files>
-use crate::types::{Entity, NextUploadPartResponse, UploadInfoResponse, UploadResponse};
+use crate::types::{
+    Entity, NextUploadPartResponse, UploadInfoData, UploadInfoLinks, UploadInfoResponse,
+    UploadResponse,
+};
 use crate::{handle_response, Client, Error, Result, Session};
 use futures::stream::poll_fn;
 use futures::task::Poll;
-use futures::{TryStream, TryStreamExt};
+use futures::{Stream, TryStream, TryStreamExt};
 use mime_guess::Mime;
 use reqwest::StatusCode;
 use serde_json::{json, Value};
 use std::io::Read;
 use std::str::FromStr;
+use tokio::io::AsyncRead;
 
 // Per the ShotGrid docs, multipart uploads should use 5Mb (minimum, save for
 // the final part) sized chunks.
 // Multipart is *required* for uploads >= 500Mb on S3 storage.
 pub const MAX_MULTIPART_CHUNK_SIZE: usize = 500 * 1024 * 1024;
 pub const MIN_MULTIPART_CHUNK_SIZE: usize = 5 * 1024 * 1024;
+/// The size at which uploads to S3 storage must be multipart.
+pub const MULTIPART_REQUIRED_SIZE: u64 = 500 * 1024 * 1024;
+
+/// The outcome of one file from [`Session::upload_many()`].
+#[derive(Debug)]
+pub struct UploadResult {
+    pub filename: String,
+    /// The `Attachment` created by the upload, if any. See
+    /// [`UploadReqBuilder::send()`].
+    pub result: Result<Option<Entity>>,
+}
+
+/// Where a multipart upload left off, so it can be picked up again with
+/// [`UploadReqBuilder::resume_from()`].
+///
+/// This is handed back by [`Error::MultipartInterrupted`] when a
+/// [resumable](`UploadReqBuilder::resumable()`) upload fails part way through.
+/// It can be serialized to hang on to it between runs, though the urls it
+/// holds are only good for as long as ShotGrid and the storage service
+/// honor them.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MultipartCheckpoint {
+    /// The info from initiating the upload, sent back to ShotGrid when
+    /// completing it.
+    pub upload_info: UploadInfoData,
+    /// The `complete_upload` link from initiating the upload.
+    pub complete_upload: String,
+    /// Where to send the next part.
+    pub upload_url: String,
+    /// Where to get the url for the part after that.
+    pub get_next_part: String,
+    /// The ETag of each part uploaded so far, in order.
+    pub etags: Vec<String>,
+    /// How many bytes of the file have been uploaded so far.
+    pub uploaded_bytes: u64,
+}
+
+/// Callback for upload progress, see [`UploadReqBuilder::on_progress()`].
+type ProgressCallback = Box<dyn Fn(u64, Option<u64>) + Send + Sync>;
 
 /// Configures a file upload request.
 ///
@@ -74,6 +118,10 @@ pub struct UploadReqBuilder<'a> {
     tags: Option<Vec<Entity>>,
     multipart: bool,
     multipart_chunk_size: usize,
+    content_length: Option<u64>,
+    on_progress: Option<ProgressCallback>,
+    resumable: bool,
+    resume_from: Option<MultipartCheckpoint>,
 }
 
 impl<'a> UploadReqBuilder<'a> {
@@ -91,23 +139,28 @@ impl<'a> UploadReqBuilder<'a> {
             field,
             filename,
             // Take a guess at the mimetype based on the original filename.
-            // If `mime_guess` doesn't have a good guess this will end up
-            // falling back to `application/octet-stream`.
+            // Extensions registered on the client win, otherwise if
+            // `mime_guess` doesn't have a good guess this will end up falling
+            // back to `application/octet-stream`.
             //
             // XXX: maybe we could open this up to the caller and make them do
             // the guessing? That's what ShotGrid did to us after all...
-            mimetype: mime_guess::from_path(filename).first(),
+            mimetype: session.client().guess_mime(filename),
             // Optional stuff
             display_name: None,
             tags: None,
             multipart: false,
             multipart_chunk_size: 10 * 1024 * 1024, // 10Mb
+            content_length: None,
+            on_progress: None,
+            resumable: false,
+            resume_from: None,
         }
     }
 
     /// Sets the text label for the attachment.
     ///
-    /// Ignored when uploading to the "images" field since this means we're
+    /// Ignored when uploading to the "image" field since this means we're
     /// uploading a thumbnail instead of an attachment.
     pub fn display_name(mut self, display_name: Option<String>) -> Self {
         self.display_name = display_name;
@@ -116,7 +169,7 @@ impl<'a> UploadReqBuilder<'a> {
 
     /// Tags to link to the attachment.
     ///
-    /// Ignored when uploading to the "images" field since this means we're
+    /// Ignored when uploading to the "image" field since this means we're
     /// uploading a thumbnail instead of an attachment.
     pub fn tags(mut self, tags: Option<Vec<Entity>>) -> Self {
         self.tags = tags;
@@ -149,6 +202,94 @@ impl<'a> UploadReqBuilder<'a> {
         self
     }
 
+    /// The size of the file content in bytes, when known up front (for
+    /// example from the metadata of a `File`).
+    ///
+    /// Uploads to S3 storage of [`MULTIPART_REQUIRED_SIZE`] or more must be
+    /// [`multipart()`](`UploadReqBuilder::multipart()`). With the size given,
+    /// a non-multipart upload that's too big fails with an
+    /// [`Error::UploadError`] before any of the file content is read.
+    /// Otherwise, the upload fails once that much content has been read.
+    ///
+    /// Since S3 doesn't accept chunked uploads, non-multipart uploads to S3
+    /// without a size have to read the entire file into memory before
+    /// sending it. With the size given, the file content is streamed as it's
+    /// read instead. The content must be exactly this many bytes, otherwise
+    /// the upload will fail.
+    pub fn content_length(mut self, bytes: u64) -> Self {
+        self.content_length = Some(bytes);
+        self
+    }
+
+    /// Sets a callback to be notified as the file content is uploaded.
+    ///
+    /// The callback is given the number of bytes sent so far, and the total
+    /// number of bytes to send *when known*. The total is known when given
+    /// with [`content_length()`](`UploadReqBuilder::content_length()`), or
+    /// when the entire file has to be read up front, which is the case for
+    /// non-multipart uploads to S3 without a `content_length()`.
+    ///
+    /// For multipart uploads the callback runs after each part is sent.
+    /// Otherwise it runs as each chunk is read from the file content.
+    ///
+    /// The callback runs on the async runtime so it should return quickly,
+    /// for example by sending the numbers to a channel or updating a progress
+    /// bar.
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(u64, Option<u64>) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// When set to `true`, a multipart upload that fails part way through is
+    /// left open on the server rather than aborted, and the `Err` is an
+    /// [`Error::MultipartInterrupted`] carrying a [`MultipartCheckpoint`] to
+    /// carry on from with [`resume_from()`](`UploadReqBuilder::resume_from()`).
+    ///
+    /// Has no effect on uploads that aren't multipart.
+    pub fn resumable(mut self, resumable: bool) -> Self {
+        self.resumable = resumable;
+        self
+    }
+
+    /// Carry on with a multipart upload from where `checkpoint` left off,
+    /// rather than starting a new one.
+    ///
+    /// The same file content must be given to `send()` again. The bytes which
+    /// were already uploaded are read past, and only the rest are sent.
+    ///
+    /// This implies [`multipart()`](`UploadReqBuilder::multipart()`) and
+    /// [`resumable()`](`UploadReqBuilder::resumable()`), so the upload can be
+    /// resumed again should it be interrupted again.
+    ///
+    /// ```no_run
+    /// # use shotgrid_rs::{Error, Session};
+    /// # async fn example(session: &Session<'_>) -> shotgrid_rs::Result<()> {
+    /// let upload = |checkpoint| async move {
+    ///     let mut req = session
+    ///         .upload("Version", 1234, Some("sg_uploaded_movie"), "sh010_v001.mov")
+    ///         .multipart(true)
+    ///         .resumable(true);
+    ///     if let Some(checkpoint) = checkpoint {
+    ///         req = req.resume_from(checkpoint);
+    ///     }
+    ///     req.send(std::fs::File::open("sh010_v001.mov")?).await
+    /// };
+    ///
+    /// let attachment = match upload(None).await {
+    ///     Err(Error::MultipartInterrupted { checkpoint, .. }) => upload(Some(*checkpoint)).await?,
+    ///     other => other?,
+    /// };
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resume_from(mut self, checkpoint: MultipartCheckpoint) -> Self {
+        self.resume_from = Some(checkpoint);
+        self
+    }
+
     /// Helper to manage the complexities of the multipart flow.
     ///
     /// > Multipart uploads are only possible if your ShotGrid server is
@@ -164,21 +305,25 @@ impl<'a> UploadReqBuilder<'a> {
     /// Each time you PUT bytes to the storage service, you must then return to
     /// ShotGrid to request the next url to PUT to.
     ///
-    /// The result of this method is either a vec of etag values (one per chunk).
-    /// In the event that any of the requests for this flow fail, the result
-    /// will be the Err from the failed request, but in addition to this, an
-    /// *abort request* will be sent to signal to ShotGrid that it should not
-    /// expect any more chunks. If the *abort request fails* the Err for that
-    /// failure will be logged as a warning (not an error).
+    /// The upload picks up from `checkpoint`, skipping over the bytes of
+    /// `file_content` it says were already uploaded. Each chunk is recorded in
+    /// `checkpoint` once it has been sent and the url for the next one is in
+    /// hand, so when this fails `checkpoint` says where to carry on from.
+    ///
+    /// On success, `checkpoint` holds the etag values (one per chunk). In the
+    /// event that any of the requests for this flow fail, the result will be
+    /// the Err from the failed request. It's up to the caller to either send
+    /// an *abort request* to signal to ShotGrid that it should not expect any
+    /// more chunks, or hang on to `checkpoint` to resume the upload later.
     async fn do_multipart_upload<S>(
         sg: &Client,
         token: &str,
         file_content: S,
         mimetype: Option<Mime>,
-        upload_url: String,
-        get_next_part: String,
+        checkpoint: &mut MultipartCheckpoint,
         chunk_size: usize,
-    ) -> Result<Vec<String>>
+        on_progress: Option<&ProgressCallback>,
+    ) -> Result<()>
     where
         S: TryStream + Send + Sync + Unpin + 'static,
         S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
@@ -186,9 +331,9 @@ impl<'a> UploadReqBuilder<'a> {
     {
         let mut file_content = file_content;
 
-        let mut upload_url = upload_url;
-        let mut get_next_part = get_next_part;
-        let mut etags: Vec<String> = vec![];
+        // When resuming, the parts already uploaded are read past without
+        // being sent again.
+        let mut to_skip = checkpoint.uploaded_bytes;
 
         // Per the docs, multipart uploads should use 5Mb (minimum, save for
         // the final part) sized chunks.
@@ -212,8 +357,6 @@ impl<'a> UploadReqBuilder<'a> {
         // Would need to be via a feature flag or some other macro like `env!()`.
         let mut body_buf = Vec::with_capacity(chunk_size);
 
-        let mut uploaded_bytes: usize = 0;
-
         // XXX: loops seem fair for this, but the signature of this method sort
         // of nods towards a recursive solution.
         // I think we should stick with the loops for now, but focus on cleanup
@@ -226,7 +369,7 @@ impl<'a> UploadReqBuilder<'a> {
         // they expect things to happen in a strict sequence).
 
         log::trace!("Consuming stream for body.");
-        let mut part_count = 0;
+        let mut part_count = checkpoint.etags.len();
         loop {
             part_count += 1;
             // This loop runs for each chunk of the file we're uploading.
@@ -251,11 +394,16 @@ impl<'a> UploadReqBuilder<'a> {
                 })? {
                     None => break,
                     Some(chunk) => {
-                        let chunk: bytes::Bytes = chunk.into();
+                        let mut chunk: bytes::Bytes = chunk.into();
                         let len = chunk.len();
                         if len == 0 {
                             break;
                         }
+                        if to_skip > 0 {
+                            let skipped = to_skip.min(len as u64);
+                            to_skip -= skipped;
+                            chunk = chunk.slice(skipped as usize..);
+                        }
                         body_buf.extend_from_slice(chunk.as_ref());
                         if body_buf.len() >= chunk_size {
                             break;
@@ -266,6 +414,11 @@ impl<'a> UploadReqBuilder<'a> {
 
             if body_buf.is_empty() {
                 log::trace!("No more bytes read from stream.");
+                if to_skip > 0 {
+                    return Err(Error::UploadError(String::from(
+                        "File content ended before the parts already uploaded.",
+                    )));
+                }
                 break;
             }
 
@@ -288,7 +441,7 @@ impl<'a> UploadReqBuilder<'a> {
             let upload_resp = {
                 let mut upload_req = sg
                     .http
-                    .put(&upload_url)
+                    .put(&checkpoint.upload_url)
                     .header("Content-Length", content_len)
                     .body(body)
                     .header("Accept", "application/json");
@@ -299,7 +452,7 @@ impl<'a> UploadReqBuilder<'a> {
 
                 log::debug!("Sending part {}, len={}", part_count, content_len);
                 // TODO: add some retries to this
-                let ret = upload_req.send().await?.error_for_status().map_err(|e| {
+                let ret = sg.send(upload_req).await?.error_for_status().map_err(|e| {
                     let reason = if let Some(status) = e.status() {
                         format!(
                             "Failed to upload chunk. Storage service responded: `{}`",
@@ -329,10 +482,13 @@ impl<'a> UploadReqBuilder<'a> {
             // payloads.
             // My initial assumption was something wrong was happening, but
             // no... it's fine.
-            etags.push(etag.to_str().unwrap().to_string());
+            let etag = etag.to_str().unwrap().to_string();
 
-            uploaded_bytes += content_len;
+            let uploaded_bytes = checkpoint.uploaded_bytes + content_len as u64;
             log::trace!("Uploaded {} ({}) bytes.", content_len, uploaded_bytes);
+            if let Some(on_progress) = on_progress {
+                on_progress(uploaded_bytes, None);
+            }
 
             // XXX: used to force a multi-part upload to fail
             // if uploaded_bytes > buf_len {
@@ -340,19 +496,20 @@ impl<'a> UploadReqBuilder<'a> {
             // }
 
             let next: NextUploadPartResponse = handle_response(
-                sg.http
-                    .get(&format!("{}{}", sg.sg_server, get_next_part))
-                    .header("Accept", "application/json")
-                    .bearer_auth(token)
-                    .send()
-                    .await?,
+                sg.send(
+                    sg.http
+                        .get(&sg.server_link(&checkpoint.get_next_part))
+                        .header("Accept", "application/json")
+                        .bearer_auth(token),
+                )
+                .await?,
             )
             .await
             .map_err(|e| {
                 Error::UploadError(format!("Failed to get next upload info. Cause: `{:?}`.", e,))
             })?;
 
-            get_next_part = next
+            let get_next_part = next
                 .links
                 .as_ref()
                 .and_then(|links| links.get_next_part.clone())
@@ -361,16 +518,21 @@ impl<'a> UploadReqBuilder<'a> {
                         "Get Next Part response missing get_next_part key.",
                     ))
                 })?;
-            upload_url = next
+            let upload_url = next
                 .links
                 .as_ref()
                 .and_then(|links| links.upload.clone())
                 .ok_or_else(|| {
                     Error::UploadError(String::from("Get Next Part response missing upload key."))
                 })?;
+
+            checkpoint.etags.push(etag);
+            checkpoint.uploaded_bytes = uploaded_bytes;
+            checkpoint.upload_url = upload_url;
+            checkpoint.get_next_part = get_next_part;
         }
 
-        Ok(etags)
+        Ok(())
     }
 
     async fn abort_multipart_upload(
@@ -380,7 +542,7 @@ impl<'a> UploadReqBuilder<'a> {
         completion_body: &Value,
     ) {
         let abort_url = format!("{}/multipart_abort", completion_url);
-        match sg
+        let req = sg
             .http
             .post(&abort_url)
             // The ShotGrid REST API spec says the body should
@@ -390,10 +552,8 @@ impl<'a> UploadReqBuilder<'a> {
             // fields as missing.
             .json(&completion_body["upload_info"])
             .bearer_auth(token)
-            .header("Accept", "application/json")
-            .send()
-            .await
-        {
+            .header("Accept", "application/json");
+        match sg.send(req).await {
             Ok(res) if !res.status().is_success() => {
                 log::warn!(
                     "Failed to properly abort multipart upload. Got status: `{}`",
@@ -407,7 +567,11 @@ impl<'a> UploadReqBuilder<'a> {
         }
     }
 
-    pub async fn send<R>(self, mut file_content: R) -> Result<()>
+    /// Upload the file, reading its content from `file_content`.
+    ///
+    /// When the upload creates an `Attachment` (ie. for uploads to a file
+    /// field, rather than thumbnails), the new `Attachment` is returned.
+    pub async fn send<R>(self, mut file_content: R) -> Result<Option<Entity>>
     where
         R: Read + Sync + Send + 'static,
     {
@@ -425,7 +589,23 @@ impl<'a> UploadReqBuilder<'a> {
         self.send_stream(read_stream).await
     }
 
-    pub async fn send_stream<S>(self, file_content: S) -> Result<()>
+    /// Like [`send()`](`UploadReqBuilder::send()`), but reads the file content
+    /// from an [`AsyncRead`] such as [`tokio::fs::File`].
+    ///
+    /// This avoids blocking the executor while reading from the source.
+    ///
+    /// [`AsyncRead`]: https://docs.rs/tokio/0.2/tokio/io/trait.AsyncRead.html
+    /// [`tokio::fs::File`]: https://docs.rs/tokio/0.2/tokio/fs/struct.File.html
+    pub async fn send_async_read<R>(self, file_content: R) -> Result<Option<Entity>>
+    where
+        R: AsyncRead + Unpin + Sync + Send + 'static,
+    {
+        let read_stream = tokio::io::reader_stream(file_content);
+        log::trace!("Async reader converted to stream.");
+        self.send_stream(read_stream).await
+    }
+
+    pub async fn send_stream<S>(self, file_content: S) -> Result<Option<Entity>>
     where
         S: TryStream + Send + Sync + Unpin + 'static,
         S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
@@ -442,8 +622,15 @@ impl<'a> UploadReqBuilder<'a> {
             tags,
             multipart,
             multipart_chunk_size,
+            content_length,
+            on_progress,
+            resumable,
+            resume_from,
         } = self;
 
+        let multipart = multipart || resume_from.is_some();
+        let resumable = resumable || resume_from.is_some();
+
         if multipart
             && !(MAX_MULTIPART_CHUNK_SIZE >= multipart_chunk_size
                 && multipart_chunk_size >= MIN_MULTIPART_CHUNK_SIZE)
@@ -467,31 +654,54 @@ impl<'a> UploadReqBuilder<'a> {
         // Some extra metadata can be set in the 3rd and final step, such as
         // setting the human readable name or associating tags with the attachment.
 
-        let init_resp: UploadInfoResponse = match field {
-            None => {
-                session
-                    .entity_upload_url_read(entity_type, entity_id, filename, Some(multipart))
-                    .await
-            }
-            Some(field) => {
-                session
-                    .entity_field_upload_url_read(
-                        entity_type,
-                        entity_id,
-                        filename,
-                        field,
-                        Some(multipart),
-                    )
-                    .await
+        //
+        // When resuming a multipart upload, the upload was initiated already
+        // and its info and links are taken from the checkpoint instead.
+
+        let (upload_info, links) = match resume_from {
+            Some(ref checkpoint) => {
+                log::trace!("Resuming upload after {} parts.", checkpoint.etags.len());
+                let links = UploadInfoLinks {
+                    upload: Some(checkpoint.upload_url.clone()),
+                    complete_upload: Some(checkpoint.complete_upload.clone()),
+                    get_next_part: Some(checkpoint.get_next_part.clone()),
+                };
+                (checkpoint.upload_info.clone(), Some(links))
             }
-        }?;
-        log::trace!("Got initial upload info.");
+            None => {
+                let init_resp: UploadInfoResponse = match field {
+                    None => {
+                        session
+                            .entity_upload_url_read(
+                                entity_type,
+                                entity_id,
+                                filename,
+                                Some(multipart),
+                            )
+                            .await
+                    }
+                    Some(field) => {
+                        session
+                            .entity_field_upload_url_read(
+                                entity_type,
+                                entity_id,
+                                filename,
+                                field,
+                                Some(multipart),
+                            )
+                            .await
+                    }
+                }?;
+                log::trace!("Got initial upload info.");
 
-        // We need to merge the data from the initial "upload info" request
-        // with the fields from the actual upload.
-        let upload_info = init_resp.data.ok_or_else(|| {
-            Error::UploadError(String::from("Upload info missing in server response."))
-        })?;
+                // We need to merge the data from the initial "upload info"
+                // request with the fields from the actual upload.
+                let upload_info = init_resp.data.ok_or_else(|| {
+                    Error::UploadError(String::from("Upload info missing in server response."))
+                })?;
+                (upload_info, init_resp.links)
+            }
+        };
 
         let upload_type: UploadType = upload_info
             .upload_type
@@ -513,25 +723,24 @@ impl<'a> UploadReqBuilder<'a> {
                 )))
             })?;
 
-        let upload_url = init_resp
-            .links
+        let upload_url = links
             .as_ref()
             .and_then(|links| links.upload.as_ref())
             .ok_or_else(|| {
                 Error::UploadError(String::from("Upload URL missing in server response."))
             })?;
 
-        let completion_url = format!(
-            "{}{}",
-            sg.sg_server,
-            init_resp
-                .links
-                .as_ref()
-                .and_then(|links| links.complete_upload.as_ref())
-                .ok_or_else(|| {
-                    Error::UploadError(String::from("Completion URL missing in server response."))
-                })?
-        );
+        let complete_upload = links
+            .as_ref()
+            .and_then(|links| links.complete_upload.clone())
+            .ok_or_else(|| {
+                Error::UploadError(String::from("Completion URL missing in server response."))
+            })?;
+        let completion_url = sg.server_link(&complete_upload);
+
+        // Only set for resumable multipart uploads, to give back if the
+        // completion request fails.
+        let mut resumable_checkpoint = None;
 
         let mut completion_body = json!({
             "upload_info": &upload_info,
@@ -542,7 +751,11 @@ impl<'a> UploadReqBuilder<'a> {
             (StorageService::SG, false) => {
                 log::trace!("Upload to SG storage.");
 
-                let body = reqwest::Body::wrap_stream(file_content);
+                let body = progress_body(
+                    file_content.map_ok(bytes::Bytes::from),
+                    on_progress,
+                    content_length,
+                );
 
                 let mut upload_req = sg
                     .http
@@ -555,7 +768,8 @@ impl<'a> UploadReqBuilder<'a> {
                     upload_req = upload_req.header("Content-Type", mimetype.as_ref());
                 }
 
-                let upload_resp: UploadResponse = handle_response(upload_req.send().await?).await?;
+                let upload_resp: UploadResponse =
+                    handle_response(sg.send(upload_req).await?).await?;
 
                 let upload_data = upload_resp.data.ok_or_else(|| {
                     Error::UploadError(String::from(
@@ -572,69 +786,113 @@ impl<'a> UploadReqBuilder<'a> {
             }
             (StorageService::S3, false) => {
                 log::trace!("Upload to S3 storage.");
-                // Since S3 doesn't support chunked encoding, we need to read
-                // the entire stream here. Yikes.
-                let body = {
-                    let mut body = vec![];
-                    let mut file_content = file_content;
-                    while let Some(chunk) = file_content.try_next().await.map_err(|_e| {
-                        // FIXME: figure out a way to share the details of the source error.
-                        //  (ON) The Err type from the TryStream needs to be downcast
-                        //  to something so we can look at it, I think.
-                        Error::UploadError(String::from("File stream read error."))
-                    })? {
-                        let chunk: bytes::Bytes = chunk.into();
-                        body.extend_from_slice(chunk.as_ref());
-                    }
-                    if body.len() > 500 * 1024 * 1024 {
-                        log::warn!("File is larger than 500Mb. Multipart upload required.");
+                if matches!(content_length, Some(len) if len >= MULTIPART_REQUIRED_SIZE) {
+                    return Err(multipart_required());
+                }
+                // S3 doesn't support chunked encoding, so the request has to
+                // have a `Content-Length`. When the caller gave us one the
+                // content can be streamed, otherwise we need to read the
+                // entire stream here to find out. Yikes.
+                let (body, body_len, on_progress) = match content_length {
+                    Some(len) => (
+                        progress_body(
+                            file_content.map_ok(bytes::Bytes::from),
+                            on_progress,
+                            Some(len),
+                        ),
+                        len,
+                        None,
+                    ),
+                    None => {
+                        let mut body = vec![];
+                        let mut file_content = file_content;
+                        while let Some(chunk) = file_content.try_next().await.map_err(|_e| {
+                            // FIXME: figure out a way to share the details of the source error.
+                            //  (ON) The Err type from the TryStream needs to be downcast
+                            //  to something so we can look at it, I think.
+                            Error::UploadError(String::from("File stream read error."))
+                        })? {
+                            let chunk: bytes::Bytes = chunk.into();
+                            body.extend_from_slice(chunk.as_ref());
+                            // No sense reading the rest of a file S3 will refuse.
+                            if body.len() as u64 >= MULTIPART_REQUIRED_SIZE {
+                                return Err(multipart_required());
+                            }
+                        }
+                        let body_len = body.len() as u64;
+                        (reqwest::Body::from(body), body_len, on_progress)
                     }
-                    body
                 };
                 // S3 uses tokens in the query string instead of auth headers.
                 let mut upload_req = sg
                     .http
                     .put(upload_url)
                     .body(body)
+                    .header("Content-Length", body_len)
                     .header("Accept", "application/json");
 
                 if let Some(ref mimetype) = mimetype {
                     upload_req = upload_req.header("Content-Type", mimetype.as_ref());
                 }
 
-                let upload_resp = upload_req.send().await?;
+                let upload_resp = sg.send(upload_req).await?;
                 // This should be a 200, but just in case AWS change their mind
                 // about signalling, we'll look for any 2xx.
                 if !upload_resp.status().is_success() {
                     return Err(Error::UploadError(String::from("S3 upload failed.")));
                 }
+                if let Some(on_progress) = on_progress {
+                    on_progress(body_len, Some(body_len));
+                }
             }
             (StorageService::S3, true) => {
                 log::trace!("Upload to S3 storage (multipart).");
-                let get_next_part = init_resp
-                    .links
-                    .as_ref()
-                    .and_then(|links| links.get_next_part.clone())
-                    .ok_or_else(|| {
-                        Error::UploadError(String::from("Init response missing get_next_part key."))
-                    })?;
+                let mut checkpoint = match resume_from {
+                    Some(checkpoint) => checkpoint,
+                    None => MultipartCheckpoint {
+                        upload_info: upload_info.clone(),
+                        complete_upload,
+                        get_next_part: links
+                            .as_ref()
+                            .and_then(|links| links.get_next_part.clone())
+                            .ok_or_else(|| {
+                                Error::UploadError(String::from(
+                                    "Init response missing get_next_part key.",
+                                ))
+                            })?,
+                        upload_url: upload_url.clone(),
+                        etags: vec![],
+                        uploaded_bytes: 0,
+                    },
+                };
 
-                let maybe_etags: Result<Vec<String>> = Self::do_multipart_upload(
+                let result = Self::do_multipart_upload(
                     sg,
                     &token,
                     file_content,
                     mimetype,
-                    upload_url.clone(),
-                    get_next_part,
+                    &mut checkpoint,
                     multipart_chunk_size,
+                    on_progress.as_ref(),
                 )
                 .await;
 
                 // Either we get a mess of etags (one per chunk) or something
                 // went wrong during the upload.
-                match maybe_etags {
-                    Ok(etags) => {
-                        completion_body["upload_info"]["etags"] = json!(etags);
+                match result {
+                    Ok(()) => {
+                        completion_body["upload_info"]["etags"] = json!(checkpoint.etags);
+                        if resumable {
+                            resumable_checkpoint = Some(checkpoint);
+                        }
+                    }
+
+                    Err(err) if resumable => {
+                        log::error!("{}", err);
+                        return Err(Error::MultipartInterrupted {
+                            checkpoint: Box::new(checkpoint),
+                            source: Box::new(err),
+                        });
                     }
 
                     Err(err) => {
@@ -678,17 +936,24 @@ impl<'a> UploadReqBuilder<'a> {
         }
 
         log::trace!("Completing upload.");
-        let completion_resp = match sg
+        let completion_req = sg
             .http
             .post(&completion_url)
             .json(&completion_body)
-            .bearer_auth(&token)
-            .send()
-            .await
-        {
+            .bearer_auth(&token);
+        let completion_resp = match sg.send(completion_req).await {
             // If the upload was multipart and the completion request fails, we
             // abort the whole thing.
             Ok(resp) if multipart && !resp.status().is_success() => {
+                if let Some(checkpoint) = resumable_checkpoint {
+                    return Err(Error::MultipartInterrupted {
+                        checkpoint: Box::new(checkpoint),
+                        source: Box::new(Error::UploadError(format!(
+                            "Got a bad status ({}) from completion endpoint.",
+                            resp.status()
+                        ))),
+                    });
+                }
                 Self::abort_multipart_upload(sg, &token, &completion_url, &completion_body).await;
 
                 return Err(Error::UploadError(format!(
@@ -699,6 +964,12 @@ impl<'a> UploadReqBuilder<'a> {
             // If there was a connection failure (or some other interruption to
             // prevent the completion request from happening, try to abort.
             Err(err) if multipart => {
+                if let Some(checkpoint) = resumable_checkpoint {
+                    return Err(Error::MultipartInterrupted {
+                        checkpoint: Box::new(checkpoint),
+                        source: Box::new(err.into()),
+                    });
+                }
                 Self::abort_multipart_upload(sg, &token, &completion_url, &completion_body).await;
 
                 return Err(Error::UploadError(format!(
@@ -733,8 +1004,147 @@ impl<'a> UploadReqBuilder<'a> {
             }
         }
 
-        Ok(())
+        // The file is uploaded by now, so a completion body we can't make
+        // sense of shouldn't fail the whole thing.
+        Ok(completion_resp
+            .bytes()
+            .await
+            .ok()
+            .and_then(|body| serde_json::from_slice::<Value>(&body).ok())
+            .and_then(|body| created_attachment(&body, field)))
+    }
+}
+
+/// Find the `Attachment` created by an upload in the body of the completion
+/// response.
+///
+/// With SG storage the response carries the new `Attachment` record itself,
+/// whereas with S3 storage it carries the record the file was uploaded to,
+/// with the `Attachment` linked in the target field.
+fn created_attachment(body: &Value, field: Option<&str>) -> Option<Entity> {
+    let data = body.get("data")?;
+    let attachment = if data["type"] == "Attachment" {
+        data
+    } else {
+        match &data[field?] {
+            // Multi-entity fields will have the new `Attachment` last.
+            Value::Array(links) => links.last()?,
+            link => link,
+        }
+    };
+
+    if attachment["type"] != "Attachment" {
+        return None;
+    }
+    let id = attachment["id"].as_i64()?;
+    Some(Entity::new("Attachment", id as i32))
+}
+
+/// Configures a thumbnail upload.
+///
+/// This is the return value from [`Session::upload_thumbnail()`]. It works the
+/// same as [`UploadReqBuilder`] but leaves out the settings which only apply to
+/// attachments, such as the display name and tags, since ShotGrid ignores them
+/// for thumbnails.
+pub struct ThumbnailUploadReqBuilder<'a> {
+    inner: UploadReqBuilder<'a>,
+}
+
+impl<'a> ThumbnailUploadReqBuilder<'a> {
+    pub(crate) fn new(
+        session: &'a Session<'a>,
+        entity_type: &'a str,
+        entity_id: i32,
+        filename: &'a str,
+    ) -> Self {
+        Self {
+            inner: UploadReqBuilder::new(session, entity_type, entity_id, Some("image"), filename),
+        }
+    }
+
+    /// See [`UploadReqBuilder::multipart()`].
+    pub fn multipart(self, multipart: bool) -> Self {
+        Self {
+            inner: self.inner.multipart(multipart),
+        }
+    }
+
+    /// See [`UploadReqBuilder::chunk_size()`].
+    pub fn chunk_size(self, bytes_per_chunk: usize) -> Self {
+        Self {
+            inner: self.inner.chunk_size(bytes_per_chunk),
+        }
+    }
+
+    /// See [`UploadReqBuilder::content_length()`].
+    pub fn content_length(self, bytes: u64) -> Self {
+        Self {
+            inner: self.inner.content_length(bytes),
+        }
+    }
+
+    /// See [`UploadReqBuilder::on_progress()`].
+    pub fn on_progress<F>(self, callback: F) -> Self
+    where
+        F: Fn(u64, Option<u64>) + Send + Sync + 'static,
+    {
+        Self {
+            inner: self.inner.on_progress(callback),
+        }
+    }
+
+    /// See [`UploadReqBuilder::send()`].
+    pub async fn send<R>(self, file_content: R) -> Result<()>
+    where
+        R: Read + Sync + Send + 'static,
+    {
+        self.inner.send(file_content).await.map(|_| ())
     }
+
+    /// See [`UploadReqBuilder::send_async_read()`].
+    pub async fn send_async_read<R>(self, file_content: R) -> Result<()>
+    where
+        R: AsyncRead + Unpin + Sync + Send + 'static,
+    {
+        self.inner.send_async_read(file_content).await.map(|_| ())
+    }
+
+    /// See [`UploadReqBuilder::send_stream()`].
+    pub async fn send_stream<S>(self, file_content: S) -> Result<()>
+    where
+        S: TryStream + Send + Sync + Unpin + 'static,
+        S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        bytes::Bytes: From<S::Ok>,
+    {
+        self.inner.send_stream(file_content).await.map(|_| ())
+    }
+}
+
+fn multipart_required() -> Error {
+    Error::UploadError(format!(
+        "Uploads to S3 of `{}` bytes or more must be multipart.",
+        MULTIPART_REQUIRED_SIZE
+    ))
+}
+
+/// Build a request body from the file content, notifying the callback (if any)
+/// as each chunk is read.
+fn progress_body<S, E>(
+    file_content: S,
+    on_progress: Option<ProgressCallback>,
+    total: Option<u64>,
+) -> reqwest::Body
+where
+    S: Stream<Item = std::result::Result<bytes::Bytes, E>> + Send + Sync + 'static,
+    E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
+{
+    let mut sent_bytes: u64 = 0;
+    reqwest::Body::wrap_stream(file_content.inspect_ok(move |chunk| {
+        sent_bytes += chunk.len() as u64;
+        if let Some(ref on_progress) = on_progress {
+            on_progress(sent_bytes, total);
+        }
+    }))
 }
 
 /// Uploads can either be direct to ShotGrid or to AWS S3.
@@ -783,7 +1193,7 @@ mod mock_tests {
     use super::*;
     use crate::Client;
     use std::io::Cursor;
-    use wiremock::matchers::{method, path};
+    use wiremock::matchers::{header, method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[tokio::test]
@@ -847,9 +1257,21 @@ mod mock_tests {
             .respond_with(ResponseTemplate::new(200).set_body_raw(upload_body, "application/json"))
             .mount(&mock_server)
             .await;
+        let complete_body = r##"
+        {
+          "data": {
+            "type": "Attachment",
+            "id": 789,
+            "display_name": "Poster art from the release of ParaNorman."
+          },
+          "links": { "self": "/api/v1/entity/attachments/789" }
+        }
+        "##;
         Mock::given(method("POST"))
             .and(path("/api/v1/entity/notes/123456/_upload"))
-            .respond_with(ResponseTemplate::new(201))
+            .respond_with(
+                ResponseTemplate::new(201).set_body_raw(complete_body, "application/json"),
+            )
             .mount(&mock_server)
             .await;
 
@@ -862,18 +1284,21 @@ mod mock_tests {
 
         let file_content: Vec<u8> = vec![];
 
-        session
+        let attachment = session
             .upload("Note", 123456, None, "paranorman-poster.jpg")
             .display_name(Some(String::from(
                 "Poster art from the release of ParaNorman.",
             )))
             .send(Cursor::new(file_content))
             .await
+            .unwrap()
             .unwrap();
+        assert_eq!(attachment.r#type, "Attachment");
+        assert_eq!(attachment.id, 789);
     }
 
     #[tokio::test]
-    async fn test_upload_attachment_s3() {
+    async fn test_upload_thumbnail_sg() {
         let mock_server = MockServer::start().await;
 
         let auth_body = r##"
@@ -889,20 +1314,31 @@ mod mock_tests {
         {{
           "data": {{
             "timestamp": "2020-11-17T03:01:01Z",
-            "upload_type": "Attachment",
+            "upload_type": "Thumbnail",
             "upload_id": null,
-            "storage_service": "s3",
+            "storage_service": "sg",
             "original_filename": "paranorman-poster.jpg",
             "multipart_upload": false
           }},
           "links": {{
-            "upload": "{}/aws/bucket/path?long-string-of-aws-stuff=1",
-            "complete_upload": "/api/v1/entity/notes/123456/_upload"
+            "upload": "{}/api/v1/entity/assets/123456/image/_upload?expiration=1605582076&filename=paranorman-poster.jpg&signature=xxxx&user_id=0000&user_type=ApiUser",
+            "complete_upload": "/api/v1/entity/assets/123456/image/_upload"
           }}
         }}
         "##,
             mock_server.uri()
         );
+        let upload_body = r##"
+        {
+          "data": {
+            "upload_id": "00000000-0000-0000-0000-000000000000",
+            "original_filename": "paranorman-poster.jpg"
+          },
+          "links": {
+            "complete_upload": "/api/v1/entity/assets/123456/image/_upload"
+          }
+        }
+        "##;
 
         Mock::given(method("POST"))
             .and(path("/api/v1/auth/access_token"))
@@ -910,22 +1346,21 @@ mod mock_tests {
             .mount(&mock_server)
             .await;
         Mock::given(method("GET"))
-            // Worth noting ShotGrid will normalize the entity name into
-            // lower-case plural in the urls it generates but this first "init"
-            // request uses the entity name we pass into `upload()` as-is.
-            .and(path("/api/v1/entity/Note/123456/_upload"))
+            .and(path("/api/v1/entity/Asset/123456/image/_upload"))
             .respond_with(ResponseTemplate::new(200).set_body_raw(init_body, "application/json"))
+            .expect(1)
             .mount(&mock_server)
             .await;
         Mock::given(method("PUT"))
-            .and(path("/aws/bucket/path"))
-            // The AWS flow gives an empty body on the upload step for some reason.
-            .respond_with(ResponseTemplate::new(200))
+            .and(path("/api/v1/entity/assets/123456/image/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(upload_body, "application/json"))
+            .expect(1)
             .mount(&mock_server)
             .await;
         Mock::given(method("POST"))
-            .and(path("/api/v1/entity/notes/123456/_upload"))
+            .and(path("/api/v1/entity/assets/123456/image/_upload"))
             .respond_with(ResponseTemplate::new(201))
+            .expect(1)
             .mount(&mock_server)
             .await;
 
@@ -939,17 +1374,14 @@ mod mock_tests {
         let file_content: Vec<u8> = vec![];
 
         session
-            .upload("Note", 123456, None, "paranorman-poster.jpg")
-            .display_name(Some(String::from(
-                "Poster art from the release of ParaNorman.",
-            )))
+            .upload_thumbnail("Asset", 123456, "paranorman-poster.jpg")
             .send(Cursor::new(file_content))
             .await
             .unwrap();
     }
 
     #[tokio::test]
-    async fn test_upload_attachment_sg_bad_tag() {
+    async fn test_upload_many_attachments_sg() {
         let mock_server = MockServer::start().await;
 
         let auth_body = r##"
@@ -968,11 +1400,11 @@ mod mock_tests {
             "upload_type": "Attachment",
             "upload_id": null,
             "storage_service": "sg",
-            "original_filename": "paranorman-poster.jpg",
+            "original_filename": "reference.jpg",
             "multipart_upload": false
           }},
           "links": {{
-            "upload": "{}/api/v1/entity/notes/123456/_upload?expiration=1605582076&filename=paranorman-poster.jpg&signature=xxxx&user_id=0000&user_type=ApiUser",
+            "upload": "{}/api/v1/entity/notes/123456/_upload?expiration=1605582076&filename=reference.jpg&signature=xxxx&user_id=0000&user_type=ApiUser",
             "complete_upload": "/api/v1/entity/notes/123456/_upload"
           }}
         }}
@@ -983,7 +1415,7 @@ mod mock_tests {
         {
           "data": {
             "upload_id": "00000000-0000-0000-0000-000000000000",
-            "original_filename": "paranorman-poster.jpg"
+            "original_filename": "reference.jpg"
           },
           "links": {
             "complete_upload": "/api/v1/entity/notes/123456/_upload"
@@ -991,74 +1423,55 @@ mod mock_tests {
         }
         "##;
 
-        let complete_body = r##"
-        {
-          "errors": [
-            {
-              "id": "00000000000000000000000000000000",
-              "status": 400,
-              "code": 104,
-              "title": "Update failed for [Attachment.tags]: Value is not legal.",
-              "source": null,
-              "detail": null,
-              "meta": null
-            }
-          ]
-        }
-        "##;
-
         Mock::given(method("POST"))
             .and(path("/api/v1/auth/access_token"))
             .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
             .mount(&mock_server)
             .await;
         Mock::given(method("GET"))
-            // Worth noting ShotGrid will normalize the entity name into
-            // lower-case plural in the urls it generates but this first "init"
-            // request uses the entity name we pass into `upload()` as-is.
             .and(path("/api/v1/entity/Note/123456/_upload"))
             .respond_with(ResponseTemplate::new(200).set_body_raw(init_body, "application/json"))
+            .expect(2)
             .mount(&mock_server)
             .await;
         Mock::given(method("PUT"))
             .and(path("/api/v1/entity/notes/123456/_upload"))
             .respond_with(ResponseTemplate::new(200).set_body_raw(upload_body, "application/json"))
+            .expect(2)
             .mount(&mock_server)
             .await;
         Mock::given(method("POST"))
             .and(path("/api/v1/entity/notes/123456/_upload"))
-            .respond_with(
-                ResponseTemplate::new(400).set_body_raw(complete_body, "application/json"),
-            )
+            .respond_with(ResponseTemplate::new(201))
+            .expect(2)
             .mount(&mock_server)
             .await;
 
         let sg = Client::new(mock_server.uri(), None, None).unwrap();
 
-        let sess = sg
+        let session = sg
             .authenticate_user("nbabcock", "iCdEAD!ppl")
             .await
             .unwrap();
 
-        let file_content: Vec<u8> = vec![];
-        let tags = vec![crate::types::Entity::new("Tag", 666)];
+        let files = vec![
+            (String::from("front.jpg"), Cursor::new(vec![1_u8, 2, 3])),
+            (String::from("side.jpg"), Cursor::new(vec![4_u8, 5, 6])),
+        ];
 
-        match sess
-            .upload("Note", 123456, None, "paranorman-poster.jpg")
-            .tags(Some(tags))
-            .send(Cursor::new(file_content))
-            .await
-        {
-            Err(Error::ServerError(errors)) => assert_eq!(errors[0].status, Some(400)),
-            other => {
-                println!("{:?}", other);
-                unreachable!()
-            }
-        }
+        let results = session.upload_many("Note", 123456, None, files, 2).await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].filename, "front.jpg");
+        assert_eq!(results[1].filename, "side.jpg");
+        assert!(results.iter().all(|r| r.result.is_ok()));
     }
 
     #[tokio::test]
-    async fn test_upload_sg_multipart_is_err() {
+    async fn test_upload_attachment_sg_progress() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+
         let mock_server = MockServer::start().await;
 
         let auth_body = r##"
@@ -1069,9 +1482,637 @@ mod mock_tests {
           "refresh_token": "xxxx"
         }
         "##;
-        let init_body = r##"
+        let init_body = format!(
+            r##"
+        {{
+          "data": {{
+            "timestamp": "2020-11-17T03:01:01Z",
+            "upload_type": "Attachment",
+            "upload_id": null,
+            "storage_service": "sg",
+            "original_filename": "paranorman-poster.jpg",
+            "multipart_upload": false
+          }},
+          "links": {{
+            "upload": "{}/api/v1/entity/notes/123456/_upload?expiration=1605582076&filename=paranorman-poster.jpg&signature=xxxx&user_id=0000&user_type=ApiUser",
+            "complete_upload": "/api/v1/entity/notes/123456/_upload"
+          }}
+        }}
+        "##,
+            mock_server.uri()
+        );
+        let upload_body = r##"
         {
-          "errors": [
+          "data": {
+            "upload_id": "00000000-0000-0000-0000-000000000000",
+            "original_filename": "paranorman-poster.jpg"
+          },
+          "links": {
+            "complete_upload": "/api/v1/entity/notes/123456/_upload"
+          }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Note/123456/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(init_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/entity/notes/123456/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(upload_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/notes/123456/_upload"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        // Bigger than the 4k read buffer used by `send()` so we get a few
+        // progress updates.
+        let file_content: Vec<u8> = vec![0; 10 * 1024];
+
+        let progress = Arc::new(AtomicU64::new(0));
+        let progress_calls = Arc::new(AtomicU64::new(0));
+
+        session
+            .upload("Note", 123456, None, "paranorman-poster.jpg")
+            .on_progress({
+                let progress = progress.clone();
+                let progress_calls = progress_calls.clone();
+                move |sent, total| {
+                    assert_eq!(total, None);
+                    progress.store(sent, Ordering::SeqCst);
+                    progress_calls.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+            .send(Cursor::new(file_content))
+            .await
+            .unwrap();
+
+        assert_eq!(progress.load(Ordering::SeqCst), 10 * 1024);
+        assert_eq!(progress_calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_upload_attachment_sg_async_read() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "xxxx",
+          "expires_in": 600,
+          "refresh_token": "xxxx"
+        }
+        "##;
+        let init_body = format!(
+            r##"
+        {{
+          "data": {{
+            "timestamp": "2020-11-17T03:01:01Z",
+            "upload_type": "Attachment",
+            "upload_id": null,
+            "storage_service": "sg",
+            "original_filename": "paranorman-poster.jpg",
+            "multipart_upload": false
+          }},
+          "links": {{
+            "upload": "{}/api/v1/entity/notes/123456/_upload?expiration=1605582076&filename=paranorman-poster.jpg&signature=xxxx&user_id=0000&user_type=ApiUser",
+            "complete_upload": "/api/v1/entity/notes/123456/_upload"
+          }}
+        }}
+        "##,
+            mock_server.uri()
+        );
+        let upload_body = r##"
+        {
+          "data": {
+            "upload_id": "00000000-0000-0000-0000-000000000000",
+            "original_filename": "paranorman-poster.jpg"
+          },
+          "links": {
+            "complete_upload": "/api/v1/entity/notes/123456/_upload"
+          }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            // Worth noting ShotGrid will normalize the entity name into
+            // lower-case plural in the urls it generates but this first "init"
+            // request uses the entity name we pass into `upload()` as-is.
+            .and(path("/api/v1/entity/Note/123456/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(init_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/entity/notes/123456/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(upload_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/notes/123456/_upload"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let file_content: &'static [u8] = b"not really a jpg";
+
+        session
+            .upload("Note", 123456, None, "paranorman-poster.jpg")
+            .display_name(Some(String::from(
+                "Poster art from the release of ParaNorman.",
+            )))
+            .send_async_read(file_content)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_upload_attachment_s3() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "xxxx",
+          "expires_in": 600,
+          "refresh_token": "xxxx"
+        }
+        "##;
+        let init_body = format!(
+            r##"
+        {{
+          "data": {{
+            "timestamp": "2020-11-17T03:01:01Z",
+            "upload_type": "Attachment",
+            "upload_id": null,
+            "storage_service": "s3",
+            "original_filename": "paranorman-poster.jpg",
+            "multipart_upload": false
+          }},
+          "links": {{
+            "upload": "{}/aws/bucket/path?long-string-of-aws-stuff=1",
+            "complete_upload": "/api/v1/entity/notes/123456/_upload"
+          }}
+        }}
+        "##,
+            mock_server.uri()
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            // Worth noting ShotGrid will normalize the entity name into
+            // lower-case plural in the urls it generates but this first "init"
+            // request uses the entity name we pass into `upload()` as-is.
+            .and(path("/api/v1/entity/Note/123456/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(init_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/aws/bucket/path"))
+            // The AWS flow gives an empty body on the upload step for some reason.
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/notes/123456/_upload"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let file_content: Vec<u8> = vec![];
+
+        session
+            .upload("Note", 123456, None, "paranorman-poster.jpg")
+            .display_name(Some(String::from(
+                "Poster art from the release of ParaNorman.",
+            )))
+            .send(Cursor::new(file_content))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_upload_s3_too_large_is_err() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "xxxx",
+          "expires_in": 600,
+          "refresh_token": "xxxx"
+        }
+        "##;
+        let init_body = format!(
+            r##"
+        {{
+          "data": {{
+            "timestamp": "2020-11-17T03:01:01Z",
+            "upload_type": "Attachment",
+            "upload_id": null,
+            "storage_service": "s3",
+            "original_filename": "paranorman.mov",
+            "multipart_upload": false
+          }},
+          "links": {{
+            "upload": "{}/aws/bucket/path?long-string-of-aws-stuff=1",
+            "complete_upload": "/api/v1/entity/notes/123456/_upload"
+          }}
+        }}
+        "##,
+            mock_server.uri()
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Note/123456/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(init_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/aws/bucket/path"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let err = session
+            .upload("Note", 123456, None, "paranorman.mov")
+            .content_length(MULTIPART_REQUIRED_SIZE)
+            .send(Cursor::new(vec![0u8; 16]))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::UploadError(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_upload_attachment_s3_streamed() {
+        use std::sync::{Arc, Mutex};
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "xxxx",
+          "expires_in": 600,
+          "refresh_token": "xxxx"
+        }
+        "##;
+        let init_body = format!(
+            r##"
+        {{
+          "data": {{
+            "timestamp": "2020-11-17T03:01:01Z",
+            "upload_type": "Attachment",
+            "upload_id": null,
+            "storage_service": "s3",
+            "original_filename": "paranorman-poster.jpg",
+            "multipart_upload": false
+          }},
+          "links": {{
+            "upload": "{}/aws/bucket/path?long-string-of-aws-stuff=1",
+            "complete_upload": "/api/v1/entity/notes/123456/_upload"
+          }}
+        }}
+        "##,
+            mock_server.uri()
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Note/123456/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(init_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/aws/bucket/path"))
+            .and(header("Content-Length", "16"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/notes/123456/_upload"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let totals = Arc::new(Mutex::new(vec![]));
+        session
+            .upload("Note", 123456, None, "paranorman-poster.jpg")
+            .content_length(16)
+            .on_progress({
+                let totals = totals.clone();
+                move |_sent, total| totals.lock().unwrap().push(total)
+            })
+            .send(Cursor::new(vec![0u8; 16]))
+            .await
+            .unwrap();
+
+        let totals = totals.lock().unwrap();
+        assert!(!totals.is_empty());
+        assert!(totals.iter().all(|total| *total == Some(16)));
+    }
+
+    #[tokio::test]
+    async fn test_upload_field_s3_returns_attachment() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "xxxx",
+          "expires_in": 600,
+          "refresh_token": "xxxx"
+        }
+        "##;
+        let init_body = format!(
+            r##"
+        {{
+          "data": {{
+            "timestamp": "2020-11-17T03:01:01Z",
+            "upload_type": "Attachment",
+            "upload_id": null,
+            "storage_service": "s3",
+            "original_filename": "sh010_comp_v001.mov",
+            "multipart_upload": false
+          }},
+          "links": {{
+            "upload": "{}/aws/bucket/path?long-string-of-aws-stuff=1",
+            "complete_upload": "/api/v1/entity/versions/123456/sg_uploaded_movie/_upload"
+          }}
+        }}
+        "##,
+            mock_server.uri()
+        );
+        let complete_body = r##"
+        {
+          "data": {
+            "type": "Version",
+            "id": 123456,
+            "sg_uploaded_movie": { "type": "Attachment", "id": 789, "name": "sh010_comp_v001.mov" }
+          },
+          "links": { "self": "/api/v1/entity/versions/123456" }
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/api/v1/entity/Version/123456/sg_uploaded_movie/_upload",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(init_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/aws/bucket/path"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(
+                "/api/v1/entity/versions/123456/sg_uploaded_movie/_upload",
+            ))
+            .respond_with(
+                ResponseTemplate::new(201).set_body_raw(complete_body, "application/json"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let file_content: Vec<u8> = vec![];
+
+        let attachment = session
+            .upload(
+                "Version",
+                123456,
+                Some("sg_uploaded_movie"),
+                "sh010_comp_v001.mov",
+            )
+            .send(Cursor::new(file_content))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(attachment.r#type, "Attachment");
+        assert_eq!(attachment.id, 789);
+    }
+
+    #[test]
+    fn test_created_attachment() {
+        // Multi-entity fields, such as `Note.attachments`.
+        let body = json!({
+            "data": {
+                "type": "Note",
+                "id": 123456,
+                "attachments": [
+                    { "type": "Attachment", "id": 788 },
+                    { "type": "Attachment", "id": 789 }
+                ]
+            }
+        });
+        assert_eq!(
+            created_attachment(&body, Some("attachments")).map(|e| e.id),
+            Some(789)
+        );
+        assert!(created_attachment(&body, None).is_none());
+        assert!(created_attachment(&json!({}), Some("attachments")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_upload_attachment_sg_bad_tag() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "xxxx",
+          "expires_in": 600,
+          "refresh_token": "xxxx"
+        }
+        "##;
+        let init_body = format!(
+            r##"
+        {{
+          "data": {{
+            "timestamp": "2020-11-17T03:01:01Z",
+            "upload_type": "Attachment",
+            "upload_id": null,
+            "storage_service": "sg",
+            "original_filename": "paranorman-poster.jpg",
+            "multipart_upload": false
+          }},
+          "links": {{
+            "upload": "{}/api/v1/entity/notes/123456/_upload?expiration=1605582076&filename=paranorman-poster.jpg&signature=xxxx&user_id=0000&user_type=ApiUser",
+            "complete_upload": "/api/v1/entity/notes/123456/_upload"
+          }}
+        }}
+        "##,
+            mock_server.uri()
+        );
+        let upload_body = r##"
+        {
+          "data": {
+            "upload_id": "00000000-0000-0000-0000-000000000000",
+            "original_filename": "paranorman-poster.jpg"
+          },
+          "links": {
+            "complete_upload": "/api/v1/entity/notes/123456/_upload"
+          }
+        }
+        "##;
+
+        let complete_body = r##"
+        {
+          "errors": [
+            {
+              "id": "00000000000000000000000000000000",
+              "status": 400,
+              "code": 104,
+              "title": "Update failed for [Attachment.tags]: Value is not legal.",
+              "source": null,
+              "detail": null,
+              "meta": null
+            }
+          ]
+        }
+        "##;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            // Worth noting ShotGrid will normalize the entity name into
+            // lower-case plural in the urls it generates but this first "init"
+            // request uses the entity name we pass into `upload()` as-is.
+            .and(path("/api/v1/entity/Note/123456/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(init_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/entity/notes/123456/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(upload_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/notes/123456/_upload"))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_raw(complete_body, "application/json"),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let sess = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let file_content: Vec<u8> = vec![];
+        let tags = vec![crate::types::Entity::new("Tag", 666)];
+
+        match sess
+            .upload("Note", 123456, None, "paranorman-poster.jpg")
+            .tags(Some(tags))
+            .send(Cursor::new(file_content))
+            .await
+        {
+            Err(Error::ServerError(errors)) => assert_eq!(errors[0].status, Some(400)),
+            other => {
+                println!("{:?}", other);
+                unreachable!()
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_sg_multipart_is_err() {
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "xxxx",
+          "expires_in": 600,
+          "refresh_token": "xxxx"
+        }
+        "##;
+        let init_body = r##"
+        {
+          "errors": [
             {
               "id": "00000000000000000000000000000000",
               "status": 400,
@@ -1247,7 +2288,298 @@ mod mock_tests {
             .multipart(true)
             .send(Cursor::new(file_content))
             .await
-            .unwrap()
+            .unwrap();
+    }
+
+    /// Mount the requests for a multipart upload to S3 where each part is
+    /// sent to the `upload` url with its `part_number` in the query.
+    async fn mock_resumable_upload(mock_server: &MockServer, failing_part: Option<&str>) {
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "xxxx",
+          "expires_in": 600,
+          "refresh_token": "xxxx"
+        }
+        "##;
+        let init_body = format!(
+            r##"
+        {{
+          "data": {{
+            "timestamp": "2020-11-17T03:01:01Z",
+            "upload_type": "Attachment",
+            "upload_id": "xxxx",
+            "storage_service": "s3",
+            "original_filename": "paranorman-poster.jpg",
+            "multipart_upload": true
+          }},
+          "links": {{
+            "complete_upload": "/api/v1/entity/notes/123456/attachments/_upload",
+            "upload": "{}/s3/paranorman-poster.jpg?part_number=1",
+            "get_next_part": "/api/v1/entity/notes/123456/attachments/_upload/multipart?part_number=2"
+          }}
+        }}
+        "##,
+            mock_server.uri()
+        );
+        let get_next_body = format!(
+            r##"
+        {{
+            "links": {{
+                "get_next_part": "/api/v1/entity/notes/123456/attachments/_upload/multipart?part_number=3",
+                "upload": "{}/s3/paranorman-poster.jpg?part_number=2"
+            }}
+        }}
+        "##,
+            mock_server.uri()
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Note/123456/attachments/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(init_body, "application/json"))
+            .mount(mock_server)
+            .await;
+        if let Some(part_number) = failing_part {
+            Mock::given(method("PUT"))
+                .and(path("/s3/paranorman-poster.jpg"))
+                .and(query_param("part_number", part_number))
+                .respond_with(ResponseTemplate::new(503))
+                .mount(mock_server)
+                .await;
+        }
+        for (part_number, etag) in &[("1", r#""abc""#), ("2", r#""def""#)] {
+            Mock::given(method("PUT"))
+                .and(path("/s3/paranorman-poster.jpg"))
+                .and(query_param("part_number", *part_number))
+                .respond_with(ResponseTemplate::new(200).insert_header("ETag", *etag))
+                .mount(mock_server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path(
+                "/api/v1/entity/notes/123456/attachments/_upload/multipart",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(get_next_body, "application/json"),
+            )
+            .mount(mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(
+                "/api/v1/entity/notes/123456/attachments/_upload/multipart_abort",
+            ))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_upload_s3_multipart_resume() {
+        let file_content = vec![1_u8; MIN_MULTIPART_CHUNK_SIZE + 10];
+
+        // The second part fails, leaving the upload open.
+        let mock_server = MockServer::start().await;
+        mock_resumable_upload(&mock_server, Some("2")).await;
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let err = session
+            .upload("Note", 123456, Some("attachments"), "paranorman-poster.jpg")
+            .multipart(true)
+            .chunk_size(MIN_MULTIPART_CHUNK_SIZE)
+            .resumable(true)
+            .send(Cursor::new(file_content.clone()))
+            .await
+            .unwrap_err();
+        let checkpoint = match err {
+            Error::MultipartInterrupted { checkpoint, .. } => checkpoint,
+            other => panic!("Expected MultipartInterrupted, got {:?}", other),
+        };
+        assert_eq!(checkpoint.etags, vec![r#""abc""#]);
+        assert_eq!(checkpoint.uploaded_bytes, MIN_MULTIPART_CHUNK_SIZE as u64);
+        assert!(checkpoint.upload_url.ends_with("part_number=2"));
+
+        // Later, against a server where the second part goes through. Only
+        // the second part is sent, and the upload isn't initiated again.
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/entity/Note/123456/attachments/_upload"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/s3/paranorman-poster.jpg"))
+            .and(query_param("part_number", "1"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+        mock_resumable_upload(&mock_server, None).await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/notes/123456/attachments/_upload"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        let mut checkpoint = *checkpoint;
+        checkpoint.upload_url = format!(
+            "{}/s3/paranorman-poster.jpg?part_number=2",
+            mock_server.uri()
+        );
+        session
+            .upload("Note", 123456, Some("attachments"), "paranorman-poster.jpg")
+            .chunk_size(MIN_MULTIPART_CHUNK_SIZE)
+            .resume_from(checkpoint)
+            .send(Cursor::new(file_content))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_upload_s3_multipart_progress() {
+        use std::sync::{Arc, Mutex};
+
+        let mock_server = MockServer::start().await;
+
+        let auth_body = r##"
+        {
+          "token_type": "Bearer",
+          "access_token": "xxxx",
+          "expires_in": 600,
+          "refresh_token": "xxxx"
+        }
+        "##;
+        let init_body = format!(
+            r##"
+        {{
+          "data": {{
+            "timestamp": "2020-11-17T03:01:01Z",
+            "upload_type": "Attachment",
+            "upload_id": "xxxx",
+            "storage_service": "s3",
+            "original_filename": "paranorman-poster.jpg",
+            "multipart_upload": true
+          }},
+          "links": {{
+            "complete_upload": "/api/v1/entity/notes/123456/attachments/_upload",
+            "upload": "{}/api/v1/entity/notes/123456/attachments/_upload?expiration=1605582076&filename=paranorman-poster.jpg&signature=xxxx&user_id=0000&user_type=ApiUser",
+            "get_next_part": "/api/v1/entity/notes/123456/attachments/_upload/multipart?filename=paranorman-poster.jpg&part_number=2&timestamp=2020-11-22T01%3A28%3A51Z&upload_id=xxxx&upload_type=Attachment"
+          }}
+        }}
+        "##,
+            mock_server.uri()
+        );
+
+        let get_next_body = format!(
+            r##"
+        {{
+            "links": {{
+                "get_next_part": "/api/v1/entity/notes/123456/attachments/_upload/multipart?filename=2020-09-24_14-17-00.mp4&part_number=3&timestamp=2020-11-22T01%3A28%3A51Z&upload_id=Wp.HwD2uVolDbye8ns2NtUW81ElvVQGTnk7dbs66dambqnb3G30_YcfsiFGWIHFdpFLTKAyDxCYWAxU6A_6mjDXRZdz0tina3pM18NJ9hsqWsmObnkkXp.4yK_nSXf97CkErsZeKqpWCvsYls9p5ew--&upload_type=Attachment",
+                "upload": "{}/api/v1/entity/notes/123456/attachments/_upload?expiration=1605582076&filename=paranorman-poster.jpg&signature=xxxx&user_id=0000&user_type=ApiUser"
+            }}
+        }}
+        "##,
+            mock_server.uri()
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/api/v1/auth/access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(auth_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            // Worth noting ShotGrid will normalize the entity name into
+            // lower-case plural in the urls it generates but this first "init"
+            // request uses the entity name we pass into `upload()` as-is.
+            .and(path("/api/v1/entity/Note/123456/attachments/_upload"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(init_body, "application/json"))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/api/v1/entity/notes/123456/attachments/_upload"))
+            // No body
+            .respond_with(ResponseTemplate::new(200).insert_header("ETag", r##""abc""##))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/api/v1/entity/notes/123456/attachments/_upload/multipart",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(get_next_body, "application/json"),
+            )
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/entity/notes/123456/attachments/_upload"))
+            .respond_with(ResponseTemplate::new(201))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(
+                "/api/v1/entity/notes/123456/attachments/_upload/multipart_abort",
+            ))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0) // a good upload should not be aborted.
+            .mount(&mock_server)
+            .await;
+
+        let sg = Client::new(mock_server.uri(), None, None).unwrap();
+
+        let session = sg
+            .authenticate_user("nbabcock", "iCdEAD!ppl")
+            .await
+            .unwrap();
+
+        // One full part plus a little extra for a second part.
+        let file_content: Vec<u8> = vec![0; MIN_MULTIPART_CHUNK_SIZE + 1024];
+        let progress = Arc::new(Mutex::new(vec![]));
+
+        session
+            .upload(
+                "Note",
+                123456,
+                // It is not currently possible to do a multipart upload without
+                // specifying a field name.
+                // This should be possible once SG-20292 has been closed in some
+                // future release of ShotGrid.
+                // <https://support.shotgunsoftware.com/hc/en-us/requests/117070>
+                Some("attachments"),
+                "paranorman-poster.jpg",
+            )
+            .multipart(true)
+            .chunk_size(MIN_MULTIPART_CHUNK_SIZE)
+            .on_progress({
+                let progress = progress.clone();
+                move |sent, total| progress.lock().unwrap().push((sent, total))
+            })
+            .send(Cursor::new(file_content))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *progress.lock().unwrap(),
+            vec![
+                (MIN_MULTIPART_CHUNK_SIZE as u64, None),
+                (MIN_MULTIPART_CHUNK_SIZE as u64 + 1024, None),
+            ]
+        );
     }
 
     #[tokio::test]
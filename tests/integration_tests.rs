@@ -19,7 +19,7 @@
 use serde_json::{json, Value};
 use shotgrid_rs::filters::{self, field, EntityRef};
 use shotgrid_rs::types::{
-    Entity, GroupingDirection, GroupingType, HierarchyEntityFields, HierarchyExpandRequest,
+    GroupingDirection, GroupingType, HierarchyEntityFields, HierarchyExpandRequest,
     HierarchySearchCriteria, HierarchySearchRequest, SummaryFieldType,
 };
 
@@ -192,7 +192,7 @@ async fn e2e_test_read_user_follows() {
 
     let user_id = helpers::get_api_user_id(&session).await;
 
-    session.user_follows_read::<Value>(user_id).await.unwrap();
+    session.user_follows_read(user_id).await.unwrap();
 }
 
 #[tokio::test]
@@ -269,7 +269,7 @@ async fn e2e_test_crud_kitchen_sink() {
     let note: Value = session
         .create(
             "Note",
-            json!({
+            &json!({
                 "subject": "shotgrid-rs test",
                 "content": "this is a test",
                 "project": { "type": "Project", "id": project_id }
@@ -297,7 +297,7 @@ async fn e2e_test_crud_kitchen_sink() {
         .update(
             "Note",
             note_id,
-            json!({"content": "test test test"}),
+            &json!({"content": "test test test"}),
             Some(&["id", "content", "created_at", "updated_at"].join(",")),
         )
         .await
@@ -352,11 +352,7 @@ async fn e2e_test_hierarchy_search_by_string() {
         .await
         .expect("Sudo As auth");
 
-    let data = HierarchySearchRequest {
-        root_path: None,
-        search_criteria: HierarchySearchCriteria::SearchString("Something".to_string()),
-        seed_entity_field: None,
-    };
+    let data = HierarchySearchRequest::new(HierarchySearchCriteria::by_string("Something"));
 
     session.hierarchy_search(data).await.unwrap();
 }
@@ -370,16 +366,9 @@ async fn e2e_test_hierarchy_search_by_entity() {
         .await
         .expect("Sudo As auth");
 
-    let data = HierarchySearchRequest {
-        root_path: None,
-        search_criteria: HierarchySearchCriteria::Entity(Entity {
-            // If the entity doesn't exist you'll get an empty result set, but
-            // that's fine for this test.
-            id: 123_456,
-            r#type: "Asset".to_string(),
-        }),
-        seed_entity_field: None,
-    };
+    // If the entity doesn't exist you'll get an empty result set, but that's
+    // fine for this test.
+    let data = HierarchySearchRequest::new(HierarchySearchCriteria::by_entity("Asset", 123_456));
 
     session.hierarchy_search(data).await.unwrap();
 }